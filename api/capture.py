@@ -4,13 +4,21 @@ import json
 import queue
 import sounddevice as sd
 
+from easy_sermon import Hymnal, Session
 from vosk import KaldiRecognizer, Model
-from api.detect import references
-from api.display import broadcast, verses
+from api.display import (
+    PARALLEL_TRANSLATIONS,
+    TRANSLATIONS,
+    broadcast,
+    parallel,
+    verses,
+)
 
 # Global settings
 SAMPLE_RATE = 16000
 MODEL_PATH = "models/vosk-model-en-us-0.42-gigaspeech"
+# Hymnal index for hymn announcements, e.g. hymnals/test_hymnal.json
+HYMNAL_PATH = os.getenv("HYMNAL", "")
 
 # Load Vosk model
 if not os.path.exists(MODEL_PATH):
@@ -21,12 +29,13 @@ recognizer = KaldiRecognizer(model, SAMPLE_RATE)
 audio_queue = queue.Queue()
 
 initial_reference = ["Genesis 1:1"]
-previous_reference = None
+session = Session()
+hymnal = Hymnal(HYMNAL_PATH) if HYMNAL_PATH else None
+# Hymns already shown in the current utterance
+shown_hymns = set()
 
 
 def transcript() -> None:
-    global previous_reference
-
     def callback(indata, frames, time, status) -> None:
         if status:
             print("", status, flush=True)
@@ -44,7 +53,8 @@ def transcript() -> None:
 
         while True:
             data = audio_queue.get()
-            if recognizer.AcceptWaveform(data):
+            final = recognizer.AcceptWaveform(data)
+            if final:
                 j = recognizer.Result()
             else:
                 j = recognizer.PartialResult()
@@ -52,23 +62,38 @@ def transcript() -> None:
             output = json.loads(j)
             text = output.get("partial") or output.get("text", "")
             text = text.strip().lower()
-            if not text:
-                continue
 
-            # Skip noise
-            if text in {"the"}:
-                text = text.replace("the", "").strip()
-                continue
-            if text in "":
+            # Skip noise, but still end the utterance so a verse or hymn
+            # mentioned again in the next one is shown again
+            if text in {"", "the"}:
+                if final:
+                    session.feed("", final=True)
+                    shown_hymns.clear()
                 continue
 
             print("🔍 Transcript:", text)
 
             # Detect Bible verses
-            for reference in references(text):
-                if reference != previous_reference:
-                    print("✅ Got:", reference)
-                    initial_reference.append(reference)
-                    previous_reference = reference
-                    full_verse = verses(reference)
-                    asyncio.run(broadcast(reference, full_verse))
+            for reference, translation in session.feed(text, final=final):
+                print("✅ Got:", reference)
+                initial_reference.append(reference)
+                full_verse = verses(reference, translation)
+                shown = TRANSLATIONS.resolve(translation)
+                if PARALLEL_TRANSLATIONS:
+                    try:
+                        passage = parallel(reference, PARALLEL_TRANSLATIONS)
+                        full_verse = passage["text"]
+                        shown = "/".join(passage["translations"])
+                    except RuntimeError as e:
+                        print("❌", e)
+                asyncio.run(broadcast(reference, full_verse, shown))
+
+            # Detect hymn announcements
+            for heading, first_line in hymnal.announcements(text) if hymnal else []:
+                if heading in shown_hymns:
+                    continue
+                shown_hymns.add(heading)
+                print("✅ Got:", heading)
+                asyncio.run(broadcast(heading, first_line))
+            if final:
+                shown_hymns.clear()
@@ -1,6 +1,10 @@
 import asyncio
 import json
+import os
 import pytest
+import time
+
+from easy_sermon import Pager, Translations, render, title_score
 
 PRO7_P_HOST = "localhost"
 PRO7_P_PORT = 54346
@@ -16,66 +20,242 @@ def unregister(ws):
     active_websockets.discard(ws)
 
 
-def load_bible(path: str) -> dict:
-    with open(path, "r", encoding="utf-8") as f:
-        raw = json.load(f)
-
-    bible = {}
-    for book in raw["books"]:
-        book_name = book["name"]
-        bible[book_name] = {}
-        for chapter in book["chapters"]:
-            chapter_num = str(chapter["chapter"])
-            bible[book_name][chapter_num] = {}
-            for verse in chapter["verses"]:
-                verse_num = str(verse["verse"])
-                text = verse["text"].strip()
-                bible[book_name][chapter_num][verse_num] = text
-    return bible
-
+TRANSLATIONS = Translations("translations")
 
-BIBLE_TEXT = load_bible("translations/akjv/akjv.json")
+# e.g. PARALLEL_TRANSLATIONS=KJV,RVR shows every verse in both
+PARALLEL_TRANSLATIONS = [
+    t.strip() for t in os.getenv("PARALLEL_TRANSLATIONS", "").split(",") if t.strip()
+]
 
 
-def verses(reference: str) -> str:
+def verses(reference: str, translation: str | None = None) -> str:
     try:
-        book, chapter_verse = reference.rsplit(" ", 1)
-        if "-" in chapter_verse:
-            chapter, verse_range = chapter_verse.split(":")
-            start_verse, end_verse = map(int, verse_range.split("-"))
-
-            lines = []
-            for v in range(start_verse, end_verse + 1):
-                verse_text = BIBLE_TEXT[book][chapter][str(v)]
-                lines.append(f"{book} {chapter}:{v} — {verse_text}")
-                print(f"{book} {chapter}:{v} — {verse_text}")
-            return "\n".join(lines)
-        else:
-            chapter, verse = chapter_verse.split(":")
-            verse_text = BIBLE_TEXT[book][chapter][verse]
-            print(f"{reference} — {verse_text}")
-            return f"{reference} — {verse_text}"
-    except Exception:
+        return TRANSLATIONS.verses(reference, translation)
+    except RuntimeError:
         return f"Verse not found: {reference}"
 
 
-async def broadcast(ref: str, verse: str) -> None:
-    await stage_display(verse)
+# Stage screen size, and seconds between pages (unset: step by hand)
+PAGE_LIMITS = {
+    "max_chars": int(os.getenv("PAGE_MAX_CHARS", "300")),
+    "max_lines": int(os.getenv("PAGE_MAX_LINES", "8")),
+    "line_width": int(os.getenv("PAGE_LINE_WIDTH", "48")),
+}
+PAGE_SECONDS = float(os.getenv("PAGE_SECONDS", "0"))
+
+# Each output's layout: placeholders {reference} {text} {translation} {page}
+# {pages} {counter}, verse numbers as reference, superscript, inline or none
+STAGE_TEMPLATE = {
+    "template": os.getenv("STAGE_TEMPLATE", "{text}"),
+    "verse_numbers": os.getenv("STAGE_VERSE_NUMBERS", "reference"),
+}
+WEB_TEMPLATE = {
+    "template": os.getenv("WEB_TEMPLATE", "{text}"),
+    "verse_numbers": os.getenv("WEB_VERSE_NUMBERS", "reference"),
+}
+AUDIENCE_TEMPLATE = {
+    "template": os.getenv("AUDIENCE_TEMPLATE", "{text}"),
+    "verse_numbers": os.getenv("AUDIENCE_VERSE_NUMBERS", "none"),
+}
+
+# ProPresenter screens that show verses: stage, audience or both. Adding
+# presentation triggers a playlist or library presentation named after the
+# reference instead, when there is one
+OUTPUTS = [o.strip() for o in os.getenv("OUTPUTS", "stage").split(",") if o.strip()]
+# Seconds to search for that presentation before sending text instead
+PRESENTATION_SEARCH_SECONDS = float(os.getenv("PRESENTATION_SEARCH_SECONDS", "1"))
+
+# The ProPresenter Message (uuid, name or index) shown on the audience screen,
+# and the text tokens its theme fills in
+AUDIENCE_MESSAGE = os.getenv("AUDIENCE_MESSAGE", "Scripture")
+AUDIENCE_REFERENCE_TOKEN = os.getenv("AUDIENCE_REFERENCE_TOKEN", "Reference")
+AUDIENCE_TEXT_TOKEN = os.getenv("AUDIENCE_TEXT_TOKEN", "Text")
+
+# Pages break in the same places whatever the template, so both step together
+reference = ""
+pager = Pager("", "")
+audience_pager = Pager("", "")
+
+# Verses detected while any of these layers is showing (a video, an
+# announcement) wait until it ends; empty shows them straight away
+HOLD_LAYERS = [
+    l.strip()
+    for l in os.getenv("HOLD_LAYERS", "media,video_input,announcements").split(",")
+    if l.strip()
+]
+STATUS_ENDPOINTS = ["status/slide", "presentation/active", "stage/message", "status/layers"]
+
+live = {}  # status url -> latest data
+held = []  # (reference, verse, translation)
+
+
+def parallel(reference: str, translations: list[str]) -> dict:
+    return TRANSLATIONS.parallel(reference, translations)
+
+
+def playlist_ids(items: list) -> list[str]:
+    """Playlist uuids, including those inside folders"""
+    ids = []
+    for item in items:
+        if item.get("field_type") == "playlist":
+            ids.append(item["id"]["uuid"])
+        ids += playlist_ids(item.get("children") or [])
+    return ids
+
+
+async def find_presentation(ref: str) -> str | None:
+    """Trigger url of the best-named presentation for ref, searching the
+    playlists (what is planned for today) before the libraries"""
+    best, best_score = None, 0
+    for playlist in playlist_ids(await fetch("v1/playlists") or []):
+        for item in (await fetch(f"v1/playlist/{playlist}") or {}).get("items", []):
+            score = title_score(item["id"]["name"], ref)
+            if score > best_score:
+                best = f"v1/playlist/{playlist}/{item['id']['index']}/trigger"
+                best_score = score
+    for library in await fetch("v1/libraries") or []:
+        contents = await fetch(f"v1/library/{library['uuid']}") or {}
+        for presentation in contents.get("items", []):
+            score = title_score(presentation["name"], ref)
+            if score > best_score:
+                best = f"v1/library/{library['uuid']}/{presentation['uuid']}/trigger"
+                best_score = score
+    return best
+
+
+async def search_presentation(ref: str) -> str | None:
+    """find_presentation, giving up after PRESENTATION_SEARCH_SECONDS"""
+    try:
+        return await asyncio.wait_for(find_presentation(ref), PRESENTATION_SEARCH_SECONDS)
+    except asyncio.TimeoutError:
+        print(f"❌ Searching ProPresenter's library took over {PRESENTATION_SEARCH_SECONDS}s")
+        return None
+
+
+async def present(ref: str, verse: str, translation: str = "") -> None:
+    """Paginate a passage and show its first page in ProPresenter"""
+    global reference, pager, audience_pager
+    if "presentation" in OUTPUTS and (trigger := await search_presentation(ref)):
+        if await send({"url": trigger, "method": "GET"}):
+            print(f"✅ {ref} is live from ProPresenter's library")
+            # ProPresenter steps the presentation's own slides
+            reference, pager, audience_pager = ref, Pager("", ""), Pager("", "")
+            return
+
+    reference = ref
+    pager = Pager(ref, verse, **PAGE_LIMITS, **STAGE_TEMPLATE, translation=translation)
+    audience_pager = Pager(
+        ref, verse, **PAGE_LIMITS, **AUDIENCE_TEMPLATE, translation=translation
+    )
+    await show_page()
+
+
+async def broadcast(ref: str, verse: str, translation: str = "") -> None:
+    if holding():
+        held.append((ref, verse, translation))
+        print(f"⏸️ Holding {ref} until the video or announcement ends")
+    else:
+        await present(ref, verse, translation)
+    text = render(
+        WEB_TEMPLATE["template"],
+        ref,
+        verse,
+        translation,
+        WEB_TEMPLATE["verse_numbers"],
+    )
     for ws in list(active_websockets):
         try:
-            await ws.send_text(json.dumps({"ref": ref, "text": verse}))
+            await ws.send_text(json.dumps({"ref": ref, "text": text}))
         except Exception:
             pass
 
 
-async def stage_display(verse: str) -> None:
-    request_obj = {
-        "url": "v1/stage/message",
-        "method": "PUT",
-        "body": verse,
-        "chunked": False,
+def holding() -> bool:
+    layers = live.get("status/layers") or {}
+    return any(layers.get(layer) for layer in HOLD_LAYERS)
+
+
+async def on_status(url: str, data) -> None:
+    """Track a status update, showing held verses once the hold ends"""
+    was_holding = holding()
+    live[url] = data
+    if was_holding and not holding():
+        while held:
+            await present(*held.pop(0))
+
+
+async def listen_status() -> None:
+    """Follow ProPresenter's status updates, reconnecting with backoff"""
+    delay = 0.25
+    request = {
+        "url": "v1/status/updates",
+        "method": "POST",
+        "body": STATUS_ENDPOINTS,
+        "chunked": True,
     }
-    request_str = json.dumps(request_obj) + "\r\n"  # CRLF-terminated JSON
+    while True:
+        try:
+            reader, writer = await asyncio.open_connection(PRO7_P_HOST, PRO7_P_PORT)
+            writer.write((json.dumps(request) + "\r\n").encode())
+            await writer.drain()
+            delay = 0.25
+            while line := await reader.readline():
+                try:
+                    update = json.loads(line)
+                except ValueError:
+                    continue
+                if "url" in update:
+                    await on_status(update["url"], update.get("data"))
+        except OSError:
+            pass
+        await asyncio.sleep(delay)
+        delay = min(delay * 2, 10)
+
+
+def status_listener() -> None:
+    if HOLD_LAYERS:
+        asyncio.run(listen_status())
+
+
+async def show_page() -> dict | None:
+    """Send the current page to each screen in OUTPUTS"""
+    if pager.current is None:
+        return None
+    label, text = pager.current
+    if "stage" in OUTPUTS:
+        await stage_display(text)
+    if "audience" in OUTPUTS and audience_pager.current is not None:
+        await audience_display(reference, audience_pager.current[1])
+    return {"label": label, "text": text, "page": pager.index + 1, "pages": len(pager.pages)}
+
+
+async def step_page(forward: bool) -> dict | None:
+    moved = pager.next() if forward else pager.previous()
+    if moved and forward:
+        audience_pager.next()
+    elif moved:
+        audience_pager.previous()
+    return await show_page() if moved else None
+
+
+async def clear_display() -> None:
+    """Take the verse off each screen in OUTPUTS"""
+    if "stage" in OUTPUTS:
+        await send({"url": "v1/stage/message", "method": "DELETE"})
+    if "audience" in OUTPUTS:
+        await send({"url": f"v1/message/{AUDIENCE_MESSAGE}/clear", "method": "GET"})
+
+
+def page_timer() -> None:
+    """Advance to the next page every PAGE_SECONDS"""
+    while PAGE_SECONDS > 0:
+        time.sleep(PAGE_SECONDS)
+        asyncio.run(step_page(forward=True))
+
+
+async def send(request: dict) -> bool:
+    """Send one request to ProPresenter; True once it is written"""
+    request_str = json.dumps({**request, "chunked": False}) + "\r\n"  # CRLF-terminated JSON
 
     try:
         reader, writer = await asyncio.wait_for(
@@ -84,14 +264,57 @@ async def stage_display(verse: str) -> None:
 
         writer.write(request_str.encode())
         await writer.drain()
-        print(f"✅ {verse} is on Stage Display")
 
         # 🚫 Don't wait for a response
         writer.close()
         await writer.wait_closed()
+        return True
 
     except Exception as e:
         print("❌ Error during send:", e)
+        return False
+
+
+async def fetch(url: str):
+    """GET from ProPresenter and return the reply's data (None on failure)"""
+    request_str = json.dumps({"url": url, "method": "GET", "chunked": False}) + "\r\n"
+
+    try:
+        reader, writer = await asyncio.wait_for(
+            asyncio.open_connection(PRO7_P_HOST, PRO7_P_PORT), timeout=3
+        )
+
+        writer.write(request_str.encode())
+        await writer.drain()
+        line = await asyncio.wait_for(reader.readline(), timeout=3)
+
+        writer.close()
+        await writer.wait_closed()
+        return json.loads(line).get("data")
+
+    except Exception as e:
+        print("❌ Error during request:", e)
+        return None
+
+
+async def stage_display(verse: str) -> None:
+    if await send({"url": "v1/stage/message", "method": "PUT", "body": verse}):
+        print(f"✅ {verse} is on Stage Display")
+
+
+async def audience_display(ref: str, text: str) -> None:
+    """Trigger AUDIENCE_MESSAGE with the reference and text tokens filled in"""
+    tokens = [
+        {"name": AUDIENCE_REFERENCE_TOKEN, "text": {"text": ref}},
+        {"name": AUDIENCE_TEXT_TOKEN, "text": {"text": text}},
+    ]
+    request = {
+        "url": f"v1/message/{AUDIENCE_MESSAGE}/trigger",
+        "method": "POST",
+        "body": tokens,
+    }
+    if await send(request):
+        print(f"✅ {ref} is on the audience screen")
 
 
 def test_offline_bible_with_an_invalid_verse() -> None:
@@ -140,6 +363,121 @@ async def test_stage_display(monkeypatch):
     await stage_display("Test verse")
 
 
+@pytest.mark.asyncio
+async def test_pages_step_forward_and_back(monkeypatch):
+    shown = []
+
+    async def dummy_stage_display(text):
+        shown.append(text)
+
+    monkeypatch.setitem(PAGE_LIMITS, "max_chars", 200)
+    monkeypatch.setitem(globals(), "stage_display", dummy_stage_display)
+
+    await broadcast("Philippians 4:6-7", verses("Philippians 4:6-7"))
+    assert len(pager.pages) == 2
+    assert pager.current[0] == "Philippians 4:6-7 (1/2)"
+
+    page = await step_page(forward=True)
+    assert page["label"] == "Philippians 4:6-7 (2/2)"
+    assert await step_page(forward=True) is None
+    assert (await step_page(forward=False))["page"] == 1
+    assert shown[-1].startswith("Philippians 4:6")
+
+
+@pytest.mark.asyncio
+async def test_audience_gets_its_own_template(monkeypatch):
+    sent = []
+
+    async def dummy_send(request):
+        sent.append(request)
+        return True
+
+    monkeypatch.setitem(globals(), "send", dummy_send)
+    monkeypatch.setitem(globals(), "OUTPUTS", ["stage", "audience"])
+
+    await broadcast("John 3:16", "John 3:16 — For God so loved the world.")
+    stage, audience = sent
+    assert stage["body"] == "John 3:16 — For God so loved the world."
+    assert audience["url"] == "v1/message/Scripture/trigger"
+    assert audience["body"][0]["text"]["text"] == "John 3:16"
+    assert audience["body"][1]["text"]["text"] == "For God so loved the world."
+
+    await clear_display()
+    assert sent[-1]["url"] == "v1/message/Scripture/clear"
+
+
+@pytest.mark.asyncio
+async def test_verses_wait_while_a_video_plays(monkeypatch):
+    shown = []
+
+    async def dummy_stage_display(text):
+        shown.append(text)
+
+    monkeypatch.setitem(globals(), "stage_display", dummy_stage_display)
+    monkeypatch.setitem(globals(), "live", {})
+
+    await on_status("status/layers", {"media": True, "slide": True})
+    await broadcast("John 3:16", "John 3:16 — For God so loved the world.")
+    assert shown == []
+    assert held[0][0] == "John 3:16"
+
+    await on_status("status/layers", {"media": False, "slide": True})
+    assert shown == ["John 3:16 — For God so loved the world."]
+    assert held == []
+
+
+@pytest.mark.asyncio
+async def test_library_presentation_or_fallback(monkeypatch):
+    replies = {
+        "v1/playlists": [
+            {
+                "id": {"uuid": "s1", "name": "Services", "index": 0},
+                "field_type": "group",
+                "children": [
+                    {"id": {"uuid": "p1", "name": "Sunday", "index": 0}, "field_type": "playlist"}
+                ],
+            }
+        ],
+        "v1/playlist/p1": {
+            "items": [{"id": {"uuid": "j", "name": "John 3_16", "index": 3}}]
+        },
+        "v1/libraries": [{"uuid": "l1", "name": "Scripture", "index": 0}],
+        "v1/library/l1": {"items": [{"uuid": "r", "name": "Romans 8:28 (KJV)", "index": 0}]},
+    }
+    sent = []
+
+    async def dummy_fetch(url):
+        return replies.get(url)
+
+    async def dummy_send(request):
+        sent.append(request)
+        return True
+
+    monkeypatch.setitem(globals(), "fetch", dummy_fetch)
+    monkeypatch.setitem(globals(), "send", dummy_send)
+    monkeypatch.setitem(globals(), "OUTPUTS", ["presentation", "stage"])
+
+    await present("John 3:16", "John 3:16 — For God so loved the world.")
+    assert sent[-1]["url"] == "v1/playlist/p1/3/trigger"
+    assert pager.current is None
+
+    await present("Romans 8:28", "Romans 8:28 — And we know...")
+    assert sent[-1]["url"] == "v1/library/l1/r/trigger"
+
+    await present("John 3:17", "John 3:17 — For God sent not his Son...")
+    assert sent[-1]["url"] == "v1/stage/message"
+
+    # A slow library sends the text instead of waiting
+    async def slow_fetch(url):
+        await asyncio.sleep(1 if url.startswith("v1/library/") else 0)
+        return replies.get(url)
+
+    monkeypatch.setitem(globals(), "fetch", slow_fetch)
+    monkeypatch.setitem(globals(), "PRESENTATION_SEARCH_SECONDS", 0.1)
+    await present("Romans 8:28", "Romans 8:28 — And we know...")
+    assert sent[-1]["url"] == "v1/stage/message"
+
+
 @pytest.mark.asyncio
 async def test_broadcast():
     # Mock WebSocket
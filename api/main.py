@@ -1,4 +1,4 @@
-from fastapi import FastAPI, Request, WebSocket
+from fastapi import FastAPI, HTTPException, Request, WebSocket
 from fastapi.responses import HTMLResponse
 from fastapi.templating import Jinja2Templates
 
@@ -9,7 +9,18 @@ import threading
 
 # import torch
 
-from api.display import register, unregister, verses
+from api.display import (
+    TRANSLATIONS,
+    broadcast,
+    clear_display,
+    page_timer,
+    parallel,
+    register,
+    status_listener,
+    step_page,
+    unregister,
+    verses,
+)
 from api.capture import transcript, initial_reference
 
 BASE_DIR = Path(__file__).resolve().parent.parent
@@ -33,6 +44,8 @@ templates = Jinja2Templates(directory=os.path.join(BASE_DIR, "templates"))
 @asynccontextmanager
 async def lifespan(app: FastAPI):
     threading.Thread(target=transcript, daemon=True).start()
+    threading.Thread(target=page_timer, daemon=True).start()
+    threading.Thread(target=status_listener, daemon=True).start()
     yield
 
 
@@ -47,6 +60,78 @@ async def home(request: Request):
     )
 
 
+@app.get("/translations")
+async def translations():
+    return {"default": TRANSLATIONS.default, "installed": TRANSLATIONS.list()}
+
+
+@app.put("/translations/default/{abbreviation}")
+async def set_default_translation(abbreviation: str):
+    try:
+        TRANSLATIONS.set_default(abbreviation)
+    except RuntimeError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    return {"default": TRANSLATIONS.default}
+
+
+@app.get("/parallel/{reference}")
+async def parallel_passage(reference: str, translations: str):
+    try:
+        return parallel(reference, translations.split(","))
+    except RuntimeError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/search")
+async def search(q: str, translation: str | None = None, limit: int = 10):
+    try:
+        return {
+            "translation": TRANSLATIONS.resolve(translation),
+            "results": TRANSLATIONS.search(q, translation, limit),
+        }
+    except RuntimeError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.get("/concordance/{word}")
+async def concordance(word: str, translation: str | None = None):
+    try:
+        return {"word": word, "references": TRANSLATIONS.concordance(word, translation)}
+    except RuntimeError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+
+
+@app.post("/display/{reference}")
+async def display(reference: str, translation: str | None = None):
+    """Show any reference (e.g. a search result) as if it had been spoken"""
+    shown = TRANSLATIONS.resolve(translation)
+    await broadcast(reference, verses(reference, translation), shown)
+    return {"reference": reference, "translation": shown}
+
+
+@app.delete("/display")
+async def clear():
+    """Take the verse off the stage and audience screens"""
+    await clear_display()
+    return {"cleared": True}
+
+
+@app.post("/pages/next")
+async def next_page():
+    page = await step_page(forward=True)
+    if page is None:
+        raise HTTPException(status_code=404, detail="No next page")
+    return page
+
+
+@app.post("/pages/previous")
+async def previous_page():
+    page = await step_page(forward=False)
+    if page is None:
+        raise HTTPException(status_code=404, detail="No previous page")
+    return page
+
+
 @app.websocket("/ws")
 async def websocket_endpoint(websocket: WebSocket):
     await websocket.accept()
@@ -4,7 +4,7 @@ use reqwest::Client;
 use std::{
     error::Error,
     fs::{self, File},
-    io::{Cursor, Write, copy},
+    io::{copy, Cursor, Write},
     path::Path,
 };
 use zip::ZipArchive;
@@ -36,6 +36,11 @@ async fn main() {
         return;
     }
 
+    if std::env::var("CARGO_FEATURE_VOSK").is_err() {
+        println!("Detection-only build, skipping model download.");
+        return;
+    }
+
     if Path::new(MODEL_FULL_PATH.as_str()).exists() {
         println!("Model already exists at {}", MODEL_FULL_PATH.as_str());
         return;
@@ -0,0 +1,463 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs};
+
+use crate::versification::Versification;
+
+/// Book names as shown and detected, with their OSIS and USFM ids, in
+/// canonical order
+#[rustfmt::skip]
+pub const CANON: [(&str, &str, &str); 66] = [
+    ("Genesis", "Gen", "GEN"), ("Exodus", "Exod", "EXO"), ("Leviticus", "Lev", "LEV"),
+    ("Numbers", "Num", "NUM"), ("Deuteronomy", "Deut", "DEU"), ("Joshua", "Josh", "JOS"),
+    ("Judges", "Judg", "JDG"), ("Ruth", "Ruth", "RUT"), ("1 Samuel", "1Sam", "1SA"),
+    ("2 Samuel", "2Sam", "2SA"), ("1 Kings", "1Kgs", "1KI"), ("2 Kings", "2Kgs", "2KI"),
+    ("1 Chronicles", "1Chr", "1CH"), ("2 Chronicles", "2Chr", "2CH"), ("Ezra", "Ezra", "EZR"),
+    ("Nehemiah", "Neh", "NEH"), ("Esther", "Esth", "EST"), ("Job", "Job", "JOB"),
+    ("Psalm", "Ps", "PSA"), ("Proverbs", "Prov", "PRO"), ("Ecclesiastes", "Eccl", "ECC"),
+    ("Song Of Solomon", "Song", "SNG"), ("Isaiah", "Isa", "ISA"), ("Jeremiah", "Jer", "JER"),
+    ("Lamentations", "Lam", "LAM"), ("Ezekiel", "Ezek", "EZK"), ("Daniel", "Dan", "DAN"),
+    ("Hosea", "Hos", "HOS"), ("Joel", "Joel", "JOL"), ("Amos", "Amos", "AMO"),
+    ("Obadiah", "Obad", "OBA"), ("Jonah", "Jonah", "JON"), ("Micah", "Mic", "MIC"),
+    ("Nahum", "Nah", "NAM"), ("Habakkuk", "Hab", "HAB"), ("Zephaniah", "Zeph", "ZEP"),
+    ("Haggai", "Hag", "HAG"), ("Zechariah", "Zech", "ZEC"), ("Malachi", "Mal", "MAL"),
+    ("Matthew", "Matt", "MAT"), ("Mark", "Mark", "MRK"), ("Luke", "Luke", "LUK"),
+    ("John", "John", "JHN"), ("Acts", "Acts", "ACT"), ("Romans", "Rom", "ROM"),
+    ("1 Corinthians", "1Cor", "1CO"), ("2 Corinthians", "2Cor", "2CO"), ("Galatians", "Gal", "GAL"),
+    ("Ephesians", "Eph", "EPH"), ("Philippians", "Phil", "PHP"), ("Colossians", "Col", "COL"),
+    ("1 Thessalonians", "1Thess", "1TH"), ("2 Thessalonians", "2Thess", "2TH"), ("1 Timothy", "1Tim", "1TI"),
+    ("2 Timothy", "2Tim", "2TI"), ("Titus", "Titus", "TIT"), ("Philemon", "Phlm", "PHM"),
+    ("Hebrews", "Heb", "HEB"), ("James", "Jas", "JAS"), ("1 Peter", "1Pet", "1PE"),
+    ("2 Peter", "2Pet", "2PE"), ("1 John", "1John", "1JN"), ("2 John", "2John", "2JN"),
+    ("3 John", "3John", "3JN"), ("Jude", "Jude", "JUD"), ("Revelation", "Rev", "REV"),
+];
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+/// Styled run of a verse's text, kept when the source marks it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub words_of_christ: bool,
+    /// Words supplied by the translators, traditionally set in italics
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub italic: bool,
+}
+
+/// Translation file format: `{"books": [{"name", "chapters": [{"chapter", "verses": [...]}]}]}`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BibleJson {
+    pub books: Vec<JsonBook>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JsonBook {
+    pub name: String,
+    pub chapters: Vec<JsonChapter>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JsonChapter {
+    pub chapter: u32,
+    pub verses: Vec<JsonVerse>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JsonVerse {
+    pub verse: u32,
+    pub text: String,
+    /// The verse starts a new paragraph
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub paragraph: bool,
+    /// `text` split into styled runs; empty when it is all plain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<Segment>,
+}
+
+/// Position of a book in its translation, e.g. Genesis is usually `BookId(0)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BookId(pub u16);
+
+#[derive(Debug)]
+struct Book {
+    name: String,
+    /// chapters[c - 1][v - 1], empty where a translation omits a verse
+    chapters: Vec<Vec<String>>,
+}
+
+/// A verse looked up by its KJV number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup<'a> {
+    Found(&'a str),
+    /// The translation numbers the chapter past this verse but leaves it out,
+    /// e.g. Matthew 17:21 in critical-text translations
+    Absent,
+    NotFound,
+}
+
+/// One translation, parsed once and shared (e.g. through `Arc`) by everything
+/// that shows verses
+#[derive(Debug)]
+pub struct BibleStore {
+    books: Vec<Book>,
+    by_name: HashMap<String, BookId>,
+    versification: Versification,
+}
+
+impl BibleStore {
+    /// Load a translation from its JSON file, e.g. translations/akjv/akjv.json
+    pub fn load(path: &str) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_json(data: &str) -> Result<Self> {
+        let raw: BibleJson = serde_json::from_str(data)?;
+
+        let mut books = Vec::with_capacity(raw.books.len());
+        let mut by_name = HashMap::new();
+
+        for book in raw.books {
+            let mut chapters: Vec<Vec<String>> = Vec::new();
+            for chapter in book.chapters {
+                let c = chapter.chapter as usize;
+                if c == 0 {
+                    continue;
+                }
+                if chapters.len() < c {
+                    chapters.resize(c, Vec::new());
+                }
+                let verses = &mut chapters[c - 1];
+                for verse in chapter.verses {
+                    let v = verse.verse as usize;
+                    if v == 0 {
+                        continue;
+                    }
+                    if verses.len() < v {
+                        verses.resize(v, String::new());
+                    }
+                    verses[v - 1] = verse.text.trim().to_string();
+                }
+            }
+            by_name.insert(book.name.clone(), BookId(books.len() as u16));
+            books.push(Book {
+                name: book.name,
+                chapters,
+            });
+        }
+
+        Ok(Self {
+            books,
+            by_name,
+            versification: Versification::Kjv,
+        })
+    }
+
+    /// The numbering this translation's text follows (KJV unless set)
+    pub fn with_versification(mut self, versification: Versification) -> Self {
+        self.versification = versification;
+        self
+    }
+
+    pub fn versification(&self) -> Versification {
+        self.versification
+    }
+
+    pub fn book(&self, name: &str) -> Option<BookId> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn book_name(&self, book: BookId) -> &str {
+        &self.books[book.0 as usize].name
+    }
+
+    pub fn verse(&self, book: BookId, chapter: u32, verse: u32) -> Option<&str> {
+        let text = self
+            .books
+            .get(book.0 as usize)?
+            .chapters
+            .get((chapter as usize).checked_sub(1)?)?
+            .get((verse as usize).checked_sub(1)?)?;
+        (!text.is_empty()).then_some(text.as_str())
+    }
+
+    /// Every verse in order as (book, chapter, verse, text), in this
+    /// translation's own numbering
+    pub fn verses(&self) -> impl Iterator<Item = (&str, u32, u32, &str)> {
+        self.books.iter().flat_map(|book| {
+            book.chapters
+                .iter()
+                .enumerate()
+                .flat_map(move |(c, verses)| {
+                    verses
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, text)| !text.is_empty())
+                        .map(move |(v, text)| {
+                            (
+                                book.name.as_str(),
+                                c as u32 + 1,
+                                v as u32 + 1,
+                                text.as_str(),
+                            )
+                        })
+                })
+        })
+    }
+
+    /// Chapter and verse in this translation's own numbering for a KJV-numbered verse
+    pub fn locate(&self, book: &str, chapter: u32, verse: u32) -> (u32, u32) {
+        self.versification.map(book, chapter, verse)
+    }
+
+    /// Look up a KJV-numbered verse, mapped into this translation's numbering
+    pub fn lookup(&self, book: &str, chapter: u32, verse: u32) -> Lookup<'_> {
+        let Some(id) = self.book(book) else {
+            return Lookup::NotFound;
+        };
+        let (chapter, verse) = self.locate(book, chapter, verse);
+        if let Some(text) = self.verse(id, chapter, verse) {
+            return Lookup::Found(text);
+        }
+
+        let verses = (chapter as usize)
+            .checked_sub(1)
+            .and_then(|c| self.books[id.0 as usize].chapters.get(c));
+        match verses {
+            Some(verses) if verse > 0 && (verse as usize) < verses.len() => Lookup::Absent,
+            _ => Lookup::NotFound,
+        }
+    }
+
+    /// Look up "John 3:16" or "John 3:16-17", one "Reference — text" line per verse
+    pub fn passage(&self, reference: &str) -> String {
+        let not_found = || format!("Verse not found: {reference}");
+
+        let Some(parsed) = Reference::parse(reference) else {
+            return not_found();
+        };
+        if self.book(&parsed.book).is_none() {
+            return not_found();
+        }
+        if !parsed.is_range()
+            && self.lookup(&parsed.book, parsed.chapter, parsed.start) == Lookup::Absent
+        {
+            return format!("Verse absent in this translation: {reference}");
+        }
+
+        let lines = parsed
+            .verses()
+            .filter_map(|v| {
+                let Lookup::Found(text) = self.lookup(&parsed.book, parsed.chapter, v) else {
+                    return None;
+                };
+                Some(if parsed.is_range() {
+                    format!("{} {}:{v} — {text}", parsed.book, parsed.chapter)
+                } else {
+                    format!("{reference} — {text}")
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if lines.is_empty() && !parsed.is_range() {
+            return not_found();
+        }
+        lines.join("\n")
+    }
+}
+
+/// A parsed "John 3:16" or "John 3:16-17"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub book: String,
+    pub chapter: u32,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Reference {
+    pub fn parse(reference: &str) -> Option<Self> {
+        let (book, chapter_verse) = reference.trim().rsplit_once(' ')?;
+        let (chapter, verses) = chapter_verse.split_once(':')?;
+        let (start, end) = match verses.split_once('-') {
+            Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+            None => {
+                let verse = verses.parse().ok()?;
+                (verse, verse)
+            }
+        };
+        Some(Self {
+            book: book.to_string(),
+            chapter: chapter.parse().ok()?,
+            start,
+            end,
+        })
+    }
+
+    pub fn is_range(&self) -> bool {
+        self.end != self.start
+    }
+
+    pub fn verses(&self) -> std::ops::RangeInclusive<u32> {
+        self.start..=self.end
+    }
+
+    /// "John 3:16" for one of the verses
+    pub fn verse_label(&self, verse: u32) -> String {
+        format!("{} {}:{verse}", self.book, self.chapter)
+    }
+}
+
+impl std::fmt::Display for Reference {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}:{}", self.book, self.chapter, self.start)?;
+        if self.is_range() {
+            write!(f, "-{}", self.end)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase words and numbers; "Psalms" counts as "Psalm"
+fn title_words(title: &str) -> Vec<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| match w.to_lowercase().as_str() {
+            "psalms" => "psalm".to_string(),
+            w => w.to_string(),
+        })
+        .collect()
+}
+
+/// How well a title, e.g. a ProPresenter presentation name, fits a
+/// reference: 2 for just the reference ("John 3_16"), 1 for the reference
+/// among other words ("John 3:16 (KJV)"), 0 otherwise. "John 3:16-18" and
+/// "1 John 3:16" do not match "John 3:16".
+pub fn title_score(title: &str, reference: &str) -> u8 {
+    let (title, reference) = (title_words(title), title_words(reference));
+    if reference.is_empty() {
+        return 0;
+    }
+    if title == reference {
+        return 2;
+    }
+    let is_number = |w: Option<&String>| w.is_some_and(|w| w.chars().all(|c| c.is_ascii_digit()));
+    let found = title
+        .windows(reference.len())
+        .enumerate()
+        .any(|(i, window)| {
+            window == reference.as_slice()
+                && !is_number(title.get(i + reference.len()))
+                && !(i > 0 && is_number(title.get(i - 1)))
+        });
+    u8::from(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_bible_and_single_verse() {
+        let path = "translations/test_bible.json";
+
+        let bible = BibleStore::load(path).unwrap();
+        let john = bible.book("John").unwrap();
+        assert_eq!(bible.book_name(john), "John");
+        assert_eq!(
+            bible.verse(john, 3, 16),
+            Some("For God so loved the world.")
+        );
+        assert_eq!(bible.verse(john, 3, 18), None);
+        assert_eq!(bible.verse(john, 1, 1), None);
+        assert_eq!(bible.verses().count(), 2);
+
+        let text = bible.passage("John 3:16");
+        assert_eq!(text, "John 3:16 — For God so loved the world.");
+    }
+
+    #[test]
+    fn test_load_bible_and_multiple_verses() {
+        let path = "translations/test_bible.json";
+
+        let bible = BibleStore::load(path).unwrap();
+
+        let text = bible.passage("John 3:16-17");
+        let expected = [
+            "John 3:16 — For God so loved the world.",
+            "John 3:17 — For God did not send his Son to condemn.",
+        ]
+        .join("\n");
+
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn test_load_bible_with_invalid_verse() {
+        let path = "translations/test_bible.json";
+
+        let bible = BibleStore::load(path).unwrap();
+
+        let text = bible.passage("NotABook 1:1");
+        assert_eq!(text, "Verse not found: NotABook 1:1");
+    }
+
+    #[test]
+    fn test_absent_and_renumbered_verses() {
+        let data = r#"{"books": [
+            {"name": "Matthew", "chapters": [{"chapter": 17, "verses": [
+                {"verse": 20, "text": "Nothing will be impossible for you."},
+                {"verse": 22, "text": "When they gathered together in Galilee..."}
+            ]}]},
+            {"name": "Psalm", "chapters": [{"chapter": 3, "verses": [
+                {"verse": 1, "text": "A Psalm of David."},
+                {"verse": 2, "text": "LORD, how are they increased that trouble me!"}
+            ]}]}
+        ]}"#;
+
+        let bible = BibleStore::from_json(data).unwrap();
+        assert_eq!(bible.lookup("Matthew", 17, 21), Lookup::Absent);
+        assert_eq!(bible.lookup("Matthew", 17, 23), Lookup::NotFound);
+        assert_eq!(bible.lookup("Matthew", 18, 1), Lookup::NotFound);
+        assert_eq!(
+            bible.passage("Matthew 17:21"),
+            "Verse absent in this translation: Matthew 17:21"
+        );
+        assert_eq!(bible.passage("Matthew 17:20-22").lines().count(), 2);
+
+        let hebrew = BibleStore::from_json(data)
+            .unwrap()
+            .with_versification(Versification::Hebrew);
+        assert_eq!(
+            hebrew.passage("Psalm 3:1"),
+            "Psalm 3:1 — LORD, how are they increased that trouble me!"
+        );
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        let range = Reference::parse("1 John 2:22-23").unwrap();
+        assert_eq!((range.book.as_str(), range.chapter), ("1 John", 2));
+        assert_eq!(range.verses().collect::<Vec<_>>(), [22, 23]);
+        assert_eq!(range.to_string(), "1 John 2:22-23");
+        assert_eq!(
+            Reference::parse("John 3:16").unwrap().to_string(),
+            "John 3:16"
+        );
+        assert_eq!(Reference::parse("John 3"), None);
+    }
+
+    #[test]
+    fn test_title_score() {
+        assert_eq!(title_score("John 3_16", "John 3:16"), 2);
+        assert_eq!(title_score("Psalms 23", "Psalm 23"), 2);
+        assert_eq!(title_score("Sermon - John 3:16 (KJV)", "John 3:16"), 1);
+        assert_eq!(title_score("John 3:16-18", "John 3:16"), 0);
+        assert_eq!(title_score("1 John 3:16", "John 3:16"), 0);
+        assert_eq!(title_score("John 3:1", "John 3:16"), 0);
+        assert_eq!(title_score("Welcome", "John 3:16"), 0);
+    }
+}
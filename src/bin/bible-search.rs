@@ -0,0 +1,81 @@
+use anyhow::{bail, Context, Result};
+use easy_sermon::output::Routing;
+use easy_sermon::paginate::PageLimits;
+use easy_sermon::propresenter::{ProPresenter, DEFAULT_ADDR};
+use easy_sermon::sink::{ProPresenterSink, Router};
+use easy_sermon::translations::{TranslationRegistry, TRANSLATIONS_DIR};
+use std::env;
+
+const USAGE: &str = "Usage: bible-search <query> [--translation KJV] [--limit 10] \
+[--dir translations] [--show N]";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut query = Vec::new();
+    let mut translation = None;
+    let mut limit = 10;
+    let mut dir = TRANSLATIONS_DIR.to_string();
+    let mut show = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--translation" => translation = iter.next().map(String::as_str),
+            "--limit" => {
+                let n = iter.next().context(USAGE)?;
+                limit = n.parse().with_context(|| format!("Invalid --limit {n}"))?;
+            }
+            "--dir" => dir = iter.next().cloned().unwrap_or(dir),
+            "--show" => {
+                let n = iter.next().context(USAGE)?;
+                show = Some(
+                    n.parse::<usize>()
+                        .with_context(|| format!("Invalid --show {n}"))?,
+                );
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => query.push(arg.as_str()),
+        }
+    }
+    if query.is_empty() {
+        bail!("{USAGE}");
+    }
+
+    let registry = TranslationRegistry::scan(&dir)?;
+    let abbreviation = registry.resolve_name(translation);
+    let hits = registry
+        .search_index(&abbreviation)?
+        .search(&query.join(" "), limit);
+
+    if hits.is_empty() {
+        println!("No verses match \"{}\" in {abbreviation}", query.join(" "));
+        return Ok(());
+    }
+    for (i, hit) in hits.iter().enumerate() {
+        println!("{:>3}. {} — {}", i + 1, hit.reference, hit.text);
+    }
+
+    if let Some(n) = show {
+        let Some(hit) = n.checked_sub(1).and_then(|i| hits.get(i)) else {
+            bail!("--show {n}: only {} results", hits.len());
+        };
+        let client = ProPresenter::new(DEFAULT_ADDR);
+        let router = Router::default().with_sink(ProPresenterSink::new(client, Routing::default()));
+        let limits = PageLimits::default();
+        let (_, reports) = router
+            .display(&registry, &hit.reference, Some(&abbreviation), &limits)
+            .await?;
+        if let Some(failed) = reports.iter().find(|r| !r.is_ok()) {
+            bail!(
+                "{}: {}",
+                failed.sink,
+                failed.error.as_deref().unwrap_or_default()
+            );
+        }
+    }
+    Ok(())
+}
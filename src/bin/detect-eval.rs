@@ -0,0 +1,100 @@
+use anyhow::{bail, Context, Result};
+use easy_sermon::detect::{bible_verse_with, DetectConfig};
+use easy_sermon::eval::{evaluate, load_corpus, Report};
+use std::{env, fs, path::Path};
+
+type Row = (&'static str, fn(&Report) -> String);
+
+const USAGE: &str = "Usage: detect-eval <transcripts-dir> [--config a.json] [--compare b.json]";
+
+fn load_config(path: Option<&String>) -> Result<(String, DetectConfig)> {
+    match path {
+        None => Ok(("default".into(), DetectConfig::default())),
+        Some(p) => {
+            let data = fs::read_to_string(p).with_context(|| format!("Failed to read {p}"))?;
+            let config = serde_json::from_str(&data).with_context(|| format!("Invalid {p}"))?;
+            let name = Path::new(p).file_stem().unwrap_or_default();
+            Ok((name.to_string_lossy().into_owned(), config))
+        }
+    }
+}
+
+fn print_confusions(name: &str, report: &Report) {
+    if !report.missed.is_empty() {
+        println!("\n❌ Missed ({name}):");
+        for c in &report.missed {
+            println!("  {}  {}  ← \"{}\"", c.source, c.reference, c.text);
+        }
+    }
+    if !report.spurious.is_empty() {
+        println!("\n⚠️ Spurious ({name}):");
+        for c in &report.spurious {
+            println!("  {}  {}  ← \"{}\"", c.source, c.reference, c.text);
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut dir = None;
+    let mut config = None;
+    let mut compare = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" => config = iter.next(),
+            "--compare" => compare = iter.next(),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ if dir.is_none() => dir = Some(arg),
+            _ => bail!("Unexpected argument: {arg}\n{USAGE}"),
+        }
+    }
+    let Some(dir) = dir else {
+        bail!("{USAGE}");
+    };
+
+    let corpus = load_corpus(Path::new(dir))?;
+    let mut runs = vec![load_config(config)?];
+    if compare.is_some() {
+        runs.push(load_config(compare)?);
+    }
+
+    let reports: Vec<(String, Report)> = runs
+        .into_iter()
+        .map(|(name, config)| {
+            let report = evaluate(&corpus, |text| bible_verse_with(text, &config));
+            (name, report)
+        })
+        .collect();
+
+    print!("{:<12}", "");
+    for (name, _) in &reports {
+        print!("{name:>16}");
+    }
+    println!();
+    let rows: [Row; 6] = [
+        ("samples", |r| r.samples.to_string()),
+        ("missed", |r| r.missed.len().to_string()),
+        ("spurious", |r| r.spurious.len().to_string()),
+        ("precision", |r| format!("{:.3}", r.precision())),
+        ("recall", |r| format!("{:.3}", r.recall())),
+        ("f1", |r| format!("{:.3}", r.f1())),
+    ];
+    for (label, value) in rows {
+        print!("{label:<12}");
+        for (_, report) in &reports {
+            print!("{:>16}", value(report));
+        }
+        println!();
+    }
+
+    for (name, report) in &reports {
+        print_confusions(name, report);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anyhow::{bail, Result};
+use easy_sermon::import::{import, install, Format};
+use easy_sermon::translations::TRANSLATIONS_DIR;
+use std::{env, path::Path};
+
+const USAGE: &str = "Usage: import-translation <source> <abbreviation> \
+[--format osis|usfm|usx|zefania] [--name NAME] [--language LANG] [--dir translations]";
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut positional = Vec::new();
+    let mut format = None;
+    let mut name = None;
+    let mut language = None;
+    let mut dir = TRANSLATIONS_DIR.to_string();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => format = iter.next().map(|f| f.parse::<Format>()).transpose()?,
+            "--name" => name = iter.next().cloned(),
+            "--language" => language = iter.next().cloned(),
+            "--dir" => dir = iter.next().cloned().unwrap_or(dir),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ if positional.len() < 2 => positional.push(arg),
+            _ => bail!("Unexpected argument: {arg}\n{USAGE}"),
+        }
+    }
+    let [source, abbreviation] = positional[..] else {
+        bail!("{USAGE}");
+    };
+
+    let mut imported = import(Path::new(source), format)?;
+    imported.meta.abbreviation = abbreviation.to_uppercase();
+    if let Some(name) = name {
+        imported.meta.name = name;
+    }
+    if let Some(language) = language {
+        imported.meta.language = language;
+    }
+    if imported.meta.name.is_empty() {
+        imported.meta.name = imported.meta.abbreviation.clone();
+    }
+    if imported.meta.language.is_empty() {
+        imported.meta.language = "en".into();
+    }
+    if imported.meta.versification.is_empty() {
+        imported.meta.versification = "kjv".into();
+    }
+
+    let path = install(&imported, Path::new(&dir))?;
+    println!(
+        "✅ {} ({}): {} books, {} verses → {}",
+        imported.meta.name,
+        imported.meta.abbreviation,
+        imported.bible.books.len(),
+        imported.verse_count(),
+        path.display()
+    );
+    Ok(())
+}
@@ -1,12 +1,52 @@
+//! Microphone input (`audio`) and offline speech recognition (`vosk`)
+
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use std::sync::{Arc, Mutex};
+use crossbeam_channel::Receiver;
 use std::thread;
 use std::time::Duration;
+
+#[cfg(feature = "vosk")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "vosk")]
 use vosk::{DecodingState, Model, Recognizer};
 
-use crate::scriptures::bible_verse;
+#[cfg(feature = "vosk")]
+use crate::detect::bible_verse;
+
+/// Stream 16-bit samples from the default input device
+pub fn microphone() -> Result<Receiver<Vec<i16>>> {
+    let (tx, rx) = crossbeam_channel::unbounded::<Vec<i16>>();
+
+    thread::spawn(move || {
+        let host = cpal::default_host();
+        let device = host.default_input_device().expect("No input device found");
+        let config = device
+            .default_input_config()
+            .expect("No default input config");
+
+        let stream = device
+            .build_input_stream(
+                &config.clone().into(),
+                move |data: &[i16], _| {
+                    tx.send(data.to_vec()).unwrap();
+                },
+                |err| eprintln!("Stream error: {err}"),
+                None,
+            )
+            .unwrap();
+
+        stream.play().unwrap();
+
+        loop {
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+
+    Ok(rx)
+}
 
+#[cfg(feature = "vosk")]
 fn process_result(json_str: &str, verses: &Arc<Mutex<Vec<String>>>) {
     let v: serde_json::Value = match serde_json::from_str(json_str) {
         Ok(val) => val,
@@ -49,7 +89,7 @@ fn process_result(json_str: &str, verses: &Arc<Mutex<Vec<String>>>) {
     }
 }
 
-#[allow(dead_code)]
+#[cfg(feature = "vosk")]
 pub fn speech_to_text() -> Result<()> {
     const SAMPLE_RATE: f32 = 16000.0;
     const MODEL_PATH: &str = "../models/vosk-model-en-us-0.42-gigaspeech";
@@ -62,37 +102,9 @@ pub fn speech_to_text() -> Result<()> {
             .ok_or_else(|| anyhow::anyhow!("Failed to create Recognizer"))?,
     ));
 
-    let (tx, rx) = crossbeam_channel::unbounded::<Vec<i16>>();
+    let rx = microphone()?;
     let verses = Arc::new(Mutex::new(Vec::<String>::new()));
 
-    {
-        let tx = tx.clone();
-        thread::spawn(move || {
-            let host = cpal::default_host();
-            let device = host.default_input_device().expect("No input device found");
-            let config = device
-                .default_input_config()
-                .expect("No default input config");
-
-            let stream = device
-                .build_input_stream(
-                    &config.clone().into(),
-                    move |data: &[i16], _| {
-                        tx.send(data.to_vec()).unwrap();
-                    },
-                    |err| eprintln!("Stream error: {err}"),
-                    None,
-                )
-                .unwrap();
-
-            stream.play().unwrap();
-
-            loop {
-                thread::sleep(Duration::from_secs(1));
-            }
-        });
-    }
-
     println!("Ready...🎙️...");
 
     for buffer in rx.iter() {
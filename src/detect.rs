@@ -1,6 +1,8 @@
 use once_cell::sync::Lazy;
-use regex::Regex;
-use std::collections::HashMap;
+use regex::{NoExpand, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 static ORDINALS: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     let mut m = HashMap::new();
@@ -22,6 +24,7 @@ static ORDINAL_RULES: Lazy<HashMap<&'static str, usize>> = Lazy::new(|| {
         ("thessalonians", 2),
         ("timothy", 2),
         ("peter", 2),
+        ("john", 3),
     ]
     .iter()
     .cloned()
@@ -34,32 +37,32 @@ static BIBLE_MAP: Lazy<HashMap<&'static str, Vec<usize>>> = Lazy::new(|| {
     // Old Testament
     map.insert("Genesis", vec![31,25,24,26,32,22,24,22,29,32,32,20,18,24,21,16,27,33,38,18,34,24,20,67,34,35,46,22,35,43,55,32,20,31,29,43,36,30,23,23,57,38,34,34,28,34,31,22,33,26]);
     map.insert("Exodus", vec![22,25,22,31,23,30,29,28,35,29,10,51,22,31,27,36,16,27,25,26,37,30,33,18,40,37,21,43,46,38,18,35,23,35,35,38,29,31,43,38]);
-    map.insert("Leviticus", vec![17,16,17,35,26,23,38,36,24,20,47,8,59,57,33,34,16,30,33,24,23,55,46,34,34,28,34]);
-    map.insert("Numbers", vec![54,34,51,49,31,27,89,26,23,36,35,16,33,45,41,35,28,32,22,29,35,41,30,25,19,36,37,27,31,33,33,35,23,34,46,34]);
+    map.insert("Leviticus", vec![17,16,17,35,19,30,38,36,24,20,47,8,59,57,33,34,16,30,37,27,24,33,44,23,55,46,34]);
+    map.insert("Numbers", vec![54,34,51,49,31,27,89,26,23,36,35,16,33,45,41,50,13,32,22,29,35,41,30,25,18,65,23,31,40,16,54,42,56,29,34,13]);
     map.insert("Deuteronomy", vec![46,37,29,49,33,25,26,20,29,22,32,31,19,29,23,22,20,22,21,20,23,29,26,22,19,19,26,68,29,20,30,52,29,12]);
     map.insert("Joshua", vec![18,24,17,24,15,27,26,35,27,43,23,24,33,15,63,10,18,28,51,9,45,34,16,33]);
     map.insert("Judges", vec![36,23,31,24,31,40,25,35,57,18,40,15,25,20,20,31,13,31,30,48,25]);
     map.insert("Ruth", vec![22,23,18,22]);
-    map.insert("1 Samuel", vec![28,36,21,22,12,21,17,22,24,31,15,25,23,52,35,23,58,30,24,42,15,23,29,22,44,25,12,25,11,31,13]);
+    map.insert("1 Samuel", vec![28,36,21,22,12,21,17,22,27,27,15,25,23,52,35,23,58,30,24,42,15,23,29,22,44,25,12,25,11,31,13]);
     map.insert("2 Samuel", vec![27,32,39,12,25,23,29,18,13,19,27,31,39,33,37,23,29,33,43,26,22,51,39,25]);
     map.insert("1 Kings", vec![53,46,28,34,18,38,51,66,28,29,43,33,34,31,34,34,24,46,21,43,29,53]);
     map.insert("2 Kings", vec![18,25,27,44,27,33,20,29,37,36,21,21,25,29,38,20,41,37,37,21,26,20,37,20,30]);
-    map.insert("1 Chronicles", vec![54,55,24,43,26,81,40,40,44,14,47,41,14,17,29,43,27,17,19,8,30,19,32,31,31,32,34,21,30]);
+    map.insert("1 Chronicles", vec![54,55,24,43,26,81,40,40,44,14,47,40,14,17,29,43,27,17,19,8,30,19,32,31,31,32,34,21,30]);
     map.insert("2 Chronicles", vec![17,18,17,22,14,42,22,18,31,19,23,16,23,14,19,14,19,34,11,37,20,12,21,27,28,23,9,27,36,27,21,33,25,33,27,23]);
     map.insert("Ezra", vec![11,70,13,24,17,22,28,36,15,44]);
     map.insert("Nehemiah", vec![11,20,32,23,19,19,73,18,38,39,36,47,31]);
     map.insert("Esther", vec![22,23,15,17,14,14,10,17,32,3]);
     map.insert("Job", vec![22,13,26,21,27,30,21,22,35,22,20,25,28,22,35,22,16,21,29,29,34,30,17,25,6,14,23,28,25,31,40,22,33,37,16,33,24,41,30,24,34,17]);
-    map.insert("Psalm", vec![6,12,8,8,12,10,17,9,20,18,7,8,6,7,5,11,15,50,14,9,13,31,6,10,22,12,14,9,11,12,24,11,22,22,28,12,40,22,13,17,13,11,5,26,17,11,9,14,20,23,19,9,6,7,23,13,11,11,17,12,8,12,11,10,13,20,7,35,36,5,24,20,28,23,10,12,20,72,13,19,16,8,18,12,13,17,7,18,52,17,16,15,5,23,11,13,12,9,9,5,8,29,22,35,45,48,43,13,31,7,10,10,9,8,18,19,2,29,176,7,8,9,4,8,5,6,5,6,8,8,3,18,3,3,21,26,9,8,24,13,10,7,12,15,21,10,20,14,9,6]);
+    map.insert("Psalm", vec![6,12,8,8,12,10,17,9,20,18,7,8,6,7,5,11,15,50,14,9,13,31,6,10,22,12,14,9,11,12,24,11,22,22,28,12,40,22,13,17,13,11,5,26,17,11,9,14,20,23,19,9,6,7,23,13,11,11,17,12,8,12,11,10,13,20,7,35,36,5,24,20,28,23,10,12,20,72,13,19,16,8,18,12,13,17,7,18,52,17,16,15,5,23,11,13,12,9,9,5,8,28,22,35,45,48,43,13,31,7,10,10,9,8,18,19,2,29,176,7,8,9,4,8,5,6,5,6,8,8,3,18,3,3,21,26,9,8,24,13,10,7,12,15,21,10,20,14,9,6]);
     map.insert("Proverbs", vec![33,22,35,27,23,35,27,36,18,32,31,28,25,35,33,33,28,24,29,30,31,29,35,34,28,28,27,28,27,33,31]);
     map.insert("Ecclesiastes", vec![18,26,22,16,20,12,29,17,18,20,10,14]);
     map.insert("Song Of Solomon", vec![17,17,11,16,16,12,14,14]);
-    map.insert("Isaiah", vec![31,22,25,6,30,13,22,22,21,34,16,6,22,32,9,14,14,7,25,6,17,25,18,23,12,21,13,29,24,33,9,20,24,17,10,22,38,22,8,31,29,25,28,28,25,13,15,22,26,11,23,15,12,17,13,12,21,14,21,22,11,12,19,12,25,24,23,23,57,30,34,34,28,34,31,22,44]);
-    map.insert("Jeremiah", vec![19,37,25,31,31,30,34,22,26,25,23,17,27,22,21,21,27,23,15,18,14,30,40,10,38,24,22,17,32,24,40,44,26,22,19,32,21,28,18,16,18,22,31,7,9,28,23,27,22,17,27,21,23,15,18,14,30,40,10,38,24,22,17,32,24,40,44,26,22,19,32,21,28,18,16,18,22,31,7,9,28,23,27,22]);
+    map.insert("Isaiah", vec![31,22,26,6,30,13,25,22,21,34,16,6,22,32,9,14,14,7,25,6,17,25,18,23,12,21,13,29,24,33,9,20,24,17,10,22,38,22,8,31,29,25,28,28,25,13,15,22,26,11,23,15,12,17,13,12,21,14,21,22,11,12,19,12,25,24]);
+    map.insert("Jeremiah", vec![19,37,25,31,31,30,34,22,26,25,23,17,27,22,21,21,27,23,15,18,14,30,40,10,38,24,22,17,32,24,40,44,26,22,19,32,21,28,18,16,18,22,13,30,5,28,7,47,39,46,64,34]);
     map.insert("Lamentations", vec![22,22,66,22,22]);
-    map.insert("Ezekiel", vec![28,10,27,17,17,14,27,18,11,22,25,28,23,23,9,27,17,22,26,20,27,31,25,24,23,35,40,23,35,27,23,34,16,33,24,23,38,23,29,49,26,20,27,31,25,24,23,35,40,23,35,27,23,34,16,33,24,23,38,23,29]);
+    map.insert("Ezekiel", vec![28,10,27,17,17,14,27,18,11,22,25,28,23,23,8,63,24,32,14,49,32,31,49,27,17,21,36,26,21,26,18,32,33,31,15,38,28,23,29,49,26,20,27,31,25,24,23,35]);
     map.insert("Daniel", vec![21,49,30,37,31,28,28,27,27,21,45,13]);
-    map.insert("Hosea", vec![11,23,5,19,15,11,16,14,17,15,12,15,11,17]);
+    map.insert("Hosea", vec![11,23,5,19,15,11,16,14,17,15,12,14,16,9]);
     map.insert("Joel", vec![20,32,21]);
     map.insert("Amos", vec![15,16,15,13,27,14,17,14,15]);
     map.insert("Obadiah", vec![21]);
@@ -69,17 +72,17 @@ static BIBLE_MAP: Lazy<HashMap<&'static str, Vec<usize>>> = Lazy::new(|| {
     map.insert("Habakkuk", vec![17,20,19]);
     map.insert("Zephaniah", vec![18,15,20]);
     map.insert("Haggai", vec![15,23]);
-    map.insert("Zechariah", vec![17,17,10,14,9,11,16,6,14,10,8,12,14,18]);
+    map.insert("Zechariah", vec![21,13,10,14,11,15,14,23,17,12,17,14,9,21]);
     map.insert("Malachi", vec![14,17,18,6]);
     // New Testament
     map.insert("Matthew", vec![25,23,17,25,48,34,29,34,38,42,30,50,58,36,39,28,27,35,30,34,46,46,39,51,46,75,66,20]);
     map.insert("Mark", vec![45,28,35,41,43,56,37,38,50,52,33,44,37,72,47,20]);
-    map.insert("Luke", vec![80,52,38,44,39,49,50,56,62,42,54,59,35,35,32,31,37,43,48,47,38,71,56,53,59,37]);
+    map.insert("Luke", vec![80,52,38,44,39,49,50,56,62,42,54,59,35,35,32,31,37,43,48,47,38,71,56,53]);
     map.insert("John", vec![51,25,36,54,47,71,53,59,41,42,57,50,38,31,27,33,26,40,42,31,25]);
     map.insert("Acts", vec![26,47,26,37,42,15,60,40,43,48,30,25,52,28,41,40,34,28,41,38,40,30,35,27,27,32,44,31]);
     map.insert("Romans", vec![32,29,31,25,21,23,25,39,33,21,36,21,14,23,33,27]);
     map.insert("1 Corinthians", vec![31,16,23,21,13,20,40,13,27,33,34,31,13,40,58,24]);
-    map.insert("2 Corinthians", vec![24,17,18,18,21,18,16,24,15,18,33,21,13]);
+    map.insert("2 Corinthians", vec![24,17,18,18,21,18,16,24,15,18,33,21,14]);
     map.insert("Galatians", vec![24,21,29,31,26,18]);
     map.insert("Ephesians", vec![23,22,21,32,33,24]);
     map.insert("Philippians", vec![30,30,21,23]);
@@ -98,7 +101,7 @@ static BIBLE_MAP: Lazy<HashMap<&'static str, Vec<usize>>> = Lazy::new(|| {
     map.insert("2 John", vec![13]);
     map.insert("3 John", vec![14]);
     map.insert("Jude", vec![25]);
-    map.insert("Revelation", vec![20,29,22,11,14,17,17,13,21,11,19,17,18,20,8,21,18,24,21,15,21,21]);
+    map.insert("Revelation", vec![20,29,22,11,14,17,17,13,21,11,19,17,18,20,8,21,18,24,21,15,27,21]);
 
     map
 });
@@ -116,86 +119,353 @@ static BIBLE_MAP: Lazy<HashMap<&'static str, Vec<usize>>> = Lazy::new(|| {
 //     Regex::new(&pat).unwrap()
 // });
 
-static REF_RE: Lazy<Regex> = Lazy::new(|| {
-    let books = BIBLE_MAP
+/// Book names without their ordinal ("1 John" → "john"), longest first
+static BOOK_NAMES: Lazy<Vec<String>> = Lazy::new(|| {
+    let mut names = BIBLE_MAP
         .keys()
-        .map(|b| regex::escape(&b.to_lowercase()))
+        .map(|b| base_name(b).to_lowercase())
+        .collect::<Vec<_>>();
+    names.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    names.dedup();
+    names
+});
+
+/// One or more number words or digits, e.g. "one hundred nineteen"
+pub(crate) static NUMBERS_PATTERN: Lazy<String> = Lazy::new(|| {
+    let mut words = word_to_num::WORDS
+        .iter()
+        .map(|(w, _)| *w)
+        .collect::<Vec<_>>();
+    words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+    let num = format!(r"(?:\d+|{})\b", words.join("|"));
+    format!(r"{num}(?:\s+{num})*")
+});
+
+static REF_RE: Lazy<Regex> = Lazy::new(|| {
+    // Verbose mode ignores literal spaces, so "song of solomon" needs `\s+`
+    let books = BOOK_NAMES
+        .iter()
+        .map(|b| regex::escape(b).replace(' ', r"\s+"))
         .collect::<Vec<_>>()
         .join("|");
 
+    let nums = NUMBERS_PATTERN.as_str();
+
     let pat = format!(
         r"(?ix)
         \b
-        (?:(\d+)\s+)?                          # optional ordinal (First, Second)
-        ({books})                              # book name
-        \s+(?:chapter\s+)?([\w\s-]+?)          # chapter (words or digits)
-        \s+(?:verses?|verse|v\.?|vs\.?)\s+     # verse keyword(s)
-        ([\w\s-]+)                             # capture verses (could include 'and', 'through', etc.)
+        (?:(\d+)\s+)?                                  # optional ordinal (First, Second)
+        ({books}|[a-z]+)                               # book name (or a word to fuzzy-match)
+        (?:\s+(?:chapter\s+)?({nums}))?                # chapter (words or digits)
+        (?:\s+(?:verses?|v\.?|vs\.?)\s+({nums}))?      # verse keyword(s) and verse
+        (?:\s*(?:-|–|—|to|through|and)\s*({nums}))?    # optional end of a range
         \b"
     );
     Regex::new(&pat).unwrap()
 });
 
+static ORDINAL_RE: Lazy<Regex> = Lazy::new(|| {
+    let words = ORDINALS
+        .keys()
+        .map(|w| regex::escape(w))
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&format!(r"(?i)\b(?:{words})\b")).unwrap()
+});
+
+/// "one john" → "1 john", but only directly before a numbered book
+static CARDINAL_RE: Lazy<Regex> = Lazy::new(|| {
+    let books = ORDINAL_RULES.keys().copied().collect::<Vec<_>>().join("|");
+    Regex::new(&format!(r"(?i)\b(one|two|three)\s+({books})\b")).unwrap()
+});
+
+static VERSES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bvs\.?\b").unwrap());
+static VERSE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bv\.?\b").unwrap());
+static HYPHEN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(twenty|thirty|forty|fifty|sixty|seventy|eighty|ninety)-([a-z]+)\b").unwrap()
+});
+
+/// Spoken names of translations, e.g. "in the NIV it reads…" → NIV
+const TRANSLATION_CUES: [(&str, &str); 17] = [
+    ("new king james", "NKJV"),
+    ("n k j v", "NKJV"),
+    ("nkjv", "NKJV"),
+    ("king james", "KJV"),
+    ("k j v", "KJV"),
+    ("kjv", "KJV"),
+    ("new international version", "NIV"),
+    ("n i v", "NIV"),
+    ("niv", "NIV"),
+    ("english standard version", "ESV"),
+    ("e s v", "ESV"),
+    ("esv", "ESV"),
+    ("new living translation", "NLT"),
+    ("n l t", "NLT"),
+    ("nlt", "NLT"),
+    ("amplified", "AMP"),
+    ("amp", "AMP"),
+];
+
+/// Any spoken translation name. Spelled-out letters ("n i v") are folded into
+/// one word before "v" can become "verse".
+static CUE_RE: Lazy<Regex> = Lazy::new(|| {
+    let mut names = TRANSLATION_CUES.iter().map(|(n, _)| *n).collect::<Vec<_>>();
+    names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+    let names = names
+        .iter()
+        .map(|n| regex::escape(n).replace(' ', r"\s+"))
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&format!(r"(?i)\b(?:{names})\b")).unwrap()
+});
+
+/// A reference found in the text, with the translation named near it (if any)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Detection {
+    pub reference: String,
+    pub translation: Option<String>,
+}
+
+/// Tunables for `bible_verse_with`, so alternatives can be measured with `detect-eval`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DetectConfig {
+    /// Minimum skim score for a misheard book name, e.g. "corinthian" → "corinthians"
+    pub fuzzy_threshold: i64,
+    /// Phrases replaced before matching, e.g. ("revelations", "revelation")
+    pub aliases: Aliases,
+    /// Accept "john three sixteen" without the word "verse"
+    pub bare_references: bool,
+    /// How many words a translation cue may be from its reference
+    pub translation_window: usize,
+}
+
+impl Default for DetectConfig {
+    fn default() -> Self {
+        Self {
+            fuzzy_threshold: 80,
+            aliases: Aliases::new(&[
+                ("psalms", "psalm"),
+                ("revelations", "revelation"),
+                ("songs of solomon", "song of solomon"),
+            ]),
+            bare_references: true,
+            translation_window: 12,
+        }
+    }
+}
+
+/// Misheard phrases and their replacements, each compiled once to a
+/// whole-word, case-insensitive pattern so ("root", "ruth") leaves "uprooted"
+/// alone
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "Vec<(String, String)>")]
+pub struct Aliases(Vec<(Regex, String)>);
+
+impl Aliases {
+    pub fn new(pairs: &[(impl AsRef<str>, impl AsRef<str>)]) -> Self {
+        let compile = |from: &str| {
+            let pattern = format!(r"(?i)\b{}\b", regex::escape(from));
+            Regex::new(&pattern).expect("an escaped alias is a valid pattern")
+        };
+        let pairs = pairs.iter();
+        Self(
+            pairs
+                .map(|(from, to)| (compile(from.as_ref()), to.as_ref().to_string()))
+                .collect(),
+        )
+    }
+
+    fn apply(&self, text: String) -> String {
+        self.0.iter().fold(text, |text, (from, to)| {
+            from.replace_all(&text, NoExpand(to)).into_owned()
+        })
+    }
+}
+
+impl From<Vec<(String, String)>> for Aliases {
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        Self::new(&pairs)
+    }
+}
+
+static DEFAULT_CONFIG: Lazy<DetectConfig> = Lazy::new(DetectConfig::default);
+
 /// - Normalize ordinals like "first" → "1", "second" → "2", etc.
-/// - Also handles "1st", "2nd", "3rd", etc.
+/// - Also handles "1st", "2nd", "3rd", and "one john" → "1 john"
 fn normalize_ordinals(text: &str) -> String {
-    let mut s = text.to_string();
-    for (word, digit) in ORDINALS.iter() {
-        let re = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(word))).unwrap();
-        s = re.replace_all(&s, *digit).into_owned();
-    }
-    s
+    let s = ORDINAL_RE.replace_all(text, |c: &regex::Captures| {
+        ORDINALS[c[0].to_lowercase().as_str()].to_string()
+    });
+    CARDINAL_RE
+        .replace_all(&s, |c: &regex::Captures| {
+            match word_to_num::parse(&c[1].to_lowercase()) {
+                Ok(n) => format!("{n} {}", &c[2]),
+                Err(_) => c[0].to_string(),
+            }
+        })
+        .into_owned()
 }
 
 ///   - vs. becomes verses, v. becomes verse
-///   - revelations becomes revelation, etc.
-fn normalize_text(input: &str) -> String {
+///   - new king james becomes nkjv
+///   - twenty-three becomes twenty three
+///   - revelations becomes revelation, etc. (see `DetectConfig::aliases`)
+fn normalize_text(input: &str, aliases: &Aliases) -> String {
     let mut text = normalize_ordinals(input);
-    text = Regex::new(r"(?i)\bvs\.?\b")
-        .unwrap()
-        .replace_all(&text, "verses")
-        .into_owned();
-    text = Regex::new(r"(?i)\bv\.?\b")
-        .unwrap()
-        .replace_all(&text, "verse")
+    text = CUE_RE
+        .replace_all(&text, |c: &regex::Captures| {
+            translation_cue(&c[0]).unwrap_or_default().to_lowercase()
+        })
         .into_owned();
+    text = VERSES_RE.replace_all(&text, "verses").into_owned();
+    text = VERSE_RE.replace_all(&text, "verse").into_owned();
+    text = HYPHEN_RE.replace_all(&text, "$1 $2").into_owned();
 
-    text.replace("psalms", "psalm")
-        .replace("revelations", "revelation")
-        .replace("songs of solomon", "song of solomon")
+    aliases.apply(text)
+}
+
+/// "1 John" → "John", "Song Of Solomon" → "Song Of Solomon"
+fn base_name(book: &str) -> &str {
+    match book.split_once(' ') {
+        Some((n, rest)) if n.parse::<usize>().is_ok() => rest,
+        _ => book,
+    }
+}
+
+/// Abbreviation for a spoken translation name, e.g. "king james" → "KJV"
+fn translation_cue(name: &str) -> Option<&'static str> {
+    let name = squash(name);
+    TRANSLATION_CUES
+        .iter()
+        .find(|(n, abbr)| *n == name || abbr.eq_ignore_ascii_case(&name))
+        .map(|(_, abbr)| *abbr)
 }
 
-#[allow(dead_code)]
-#[allow(clippy::collapsible_if)]
 /// Extract a Bible verse from an input text
 pub fn bible_verse(input: &str) -> Vec<String> {
-    let text = normalize_text(input);
+    bible_verse_with(input, &DEFAULT_CONFIG)
+}
+
+/// `bible_verse` with explicit tunables
+pub fn bible_verse_with(input: &str, config: &DetectConfig) -> Vec<String> {
+    detect_with(input, config)
+        .into_iter()
+        .map(|d| d.reference)
+        .collect()
+}
+
+/// Extract Bible verses along with any translation named near them,
+/// e.g. "the amplified says in romans eight verse one"
+pub fn detect(input: &str) -> Vec<Detection> {
+    detect_with(input, &DEFAULT_CONFIG)
+}
+
+/// `detect` with explicit tunables
+pub fn detect_with(input: &str, config: &DetectConfig) -> Vec<Detection> {
+    let text = normalize_text(input, &config.aliases);
+
+    // Cues were folded into single words ("nkjv") by normalize_text
+    let cues = CUE_RE
+        .find_iter(&text)
+        .filter_map(|m| Some((m.range(), translation_cue(m.as_str())?)))
+        .collect::<Vec<_>>();
+
+    find_references(&text, config)
+        .into_iter()
+        .map(|(reference, span)| {
+            let translation = cues
+                .iter()
+                .filter_map(|(cue, abbr)| {
+                    let gap = if cue.end <= span.start {
+                        &text[cue.end..span.start]
+                    } else if cue.start >= span.end {
+                        &text[span.end..cue.start]
+                    } else {
+                        return None;
+                    };
+                    let words = gap.split_whitespace().count();
+                    (words <= config.translation_window).then_some((words, *abbr))
+                })
+                .min_by_key(|(words, _)| *words)
+                .map(|(_, abbr)| abbr.to_string());
+            Detection {
+                reference,
+                translation,
+            }
+        })
+        .collect()
+}
+
+/// Detection over a live transcript. Partial results repeat every word
+/// since the recognizer last finalized, so each reference is reported once
+/// per utterance; after a final result it can be reported again.
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    config: DetectConfig,
+    history: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl Session {
+    pub fn new(config: DetectConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
 
+    /// New detections in the next partial transcript
+    pub fn feed(&mut self, text: &str) -> Vec<Detection> {
+        let mut fresh = Vec::new();
+        for detection in detect_with(text, &self.config) {
+            if self.seen.insert(detection.reference.clone()) {
+                self.history.push(detection.reference.clone());
+                fresh.push(detection);
+            }
+        }
+        fresh
+    }
+
+    /// New detections in a final transcript, which ends the utterance
+    pub fn feed_final(&mut self, text: &str) -> Vec<Detection> {
+        let fresh = self.feed(text);
+        self.seen.clear();
+        fresh
+    }
+
+    /// Every reference reported so far, oldest first
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+}
+
+/// References in normalized text, with where each was spoken
+fn find_references(text: &str, config: &DetectConfig) -> Vec<(String, Range<usize>)> {
     let mut results = Vec::new();
-    for cap in REF_RE.captures_iter(&text) {
-        let ord_raw = cap.get(1).map(|m| m.as_str());
+    let mut pos = 0;
+    while let Some(cap) = REF_RE.captures_at(text, pos) {
+        // A rejected match only consumes its first word, so "in" can't swallow "in 1 john"
+        pos = cap.get(2).unwrap().end();
+
+        let ord_num = cap.get(1).and_then(|o| o.as_str().parse::<usize>().ok());
         let book_raw = cap.get(2).unwrap().as_str();
-        let ord_num = ord_raw.and_then(|o| o.parse::<usize>().ok());
 
-        let fuzzy = match fuzzy_book_match(book_raw) {
+        // Without "verse", only take an exact book name ("john three sixteen")
+        let bare = cap.get(4).is_none();
+        if bare && (!config.bare_references || !BOOK_NAMES.contains(&squash(book_raw))) {
+            continue;
+        }
+
+        let fuzzy = match fuzzy_book_match(book_raw, config.fuzzy_threshold) {
             Some(f) => f,
             None => continue,
         };
 
-        let book_key = if fuzzy == "john" {
-            if let Some(n) = ord_num {
-                format!("{n} john")
-            } else {
-                "john".into()
-            }
-        } else if let Some(&max_ord) = ORDINAL_RULES.get(fuzzy.as_str()) {
-            let n = ord_num.unwrap_or(0);
-            if n == 0 || n > max_ord {
-                continue;
-            }
-            format!("{n} {fuzzy}")
-        } else {
-            fuzzy.clone()
+        // "corinthians" without an ordinal falls through and fails the lookup below
+        let book_key = match (ord_num, ORDINAL_RULES.get(fuzzy.as_str())) {
+            (Some(n), Some(&max_ord)) if (1..=max_ord).contains(&n) => format!("{n} {fuzzy}"),
+            _ => fuzzy.clone(),
         };
 
         let book = book_key
@@ -210,47 +480,40 @@ pub fn bible_verse(input: &str) -> Vec<String> {
             .collect::<Vec<_>>()
             .join(" ");
 
-        // parse chapter
-        let chap_raw = cap.get(3).unwrap().as_str().trim();
-        let chap_n: usize = match word_to_number(chap_raw).and_then(|s| s.parse::<usize>().ok()) {
-            Some(n) => n,
+        let verses = match BIBLE_MAP.get(book.as_str()) {
+            Some(v) => v,
             None => continue,
         };
 
-        // parse verse(s)
-        let verse_raw = cap.get(4).unwrap().as_str().to_lowercase();
-        let normalized = verse_raw
-            .replace(" through ", "-")
-            .replace(" to ", "-")
-            .replace(" and ", "-");
-
-        let parts: Vec<&str> = normalized
-            .split('-')
-            .map(str::trim)
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        let start_raw = parts.first().copied().unwrap_or("");
-        let end_raw = parts.get(1).copied();
-
-        let start_n: usize = match word_to_number(start_raw).and_then(|s| s.parse::<usize>().ok()) {
-            Some(n) => n,
-            None => continue,
-        };
-        let end_n: Option<usize> = if let Some(end_str) = end_raw {
-            match word_to_number(end_str).and_then(|s| s.parse::<usize>().ok()) {
-                Some(n) => Some(n),
+        // parse chapter & verse
+        let chap_raw = cap.get(3).map(|m| m.as_str());
+        let verse_raw = cap.get(4).map(|m| m.as_str());
+        let (chap_n, start_n) = match (chap_raw, verse_raw) {
+            (Some(c), Some(v)) => match (word_to_number(c), word_to_number(v)) {
+                (Some(c), Some(v)) => (c, v),
+                _ => continue,
+            },
+            // "jude verse three"
+            (None, Some(v)) if verses.len() == 1 => match word_to_number(v) {
+                Some(v) => (1, v),
                 None => continue,
-            }
-        } else {
-            None
+            },
+            // "john three sixteen"
+            (Some(c), None) => match number_groups(c).as_deref() {
+                Some(&[c, v]) => (c, v),
+                Some(&[v]) if verses.len() == 1 => (1, v),
+                _ => continue,
+            },
+            _ => continue,
         };
 
-        // validate against BIBLE_MAP
-        let verses = match BIBLE_MAP.get(book.as_str()) {
-            Some(v) => v,
-            None => continue,
+        let end_n = match cap.get(5).map(|m| word_to_number(m.as_str())) {
+            Some(None) => continue,
+            Some(end) => end,
+            None => None,
         };
+
+        // validate against BIBLE_MAP
         if chap_n == 0 || chap_n > verses.len() {
             continue;
         }
@@ -264,125 +527,296 @@ pub fn bible_verse(input: &str) -> Vec<String> {
                 reference = format!("{reference}-{e}");
             }
         }
-        results.push(reference);
+        let span = cap.get(0).unwrap().range();
+        pos = span.end;
+        results.push((reference, span));
     }
 
     results
 }
 
+/// All the ways a reference can be read aloud, e.g. "1 John 1:9" →
+/// "first john one verse nine", "one john one nine", etc.
+///
+/// Readings that `bible_verse` can't tell apart (e.g. "john twenty one" for
+/// John 20:1) are left out. Returns nothing for an invalid reference.
+pub fn spoken_forms(reference: &str) -> Vec<String> {
+    let Some((book, chapter_verse)) = reference.rsplit_once(' ') else {
+        return vec![];
+    };
+    let Some((chapter, verse_part)) = chapter_verse.split_once(':') else {
+        return vec![];
+    };
+    let (start, end) = match verse_part.split_once('-') {
+        Some((s, e)) => (s, Some(e)),
+        None => (verse_part, None),
+    };
+    let (Ok(c), Ok(v)) = (chapter.parse::<usize>(), start.parse::<usize>()) else {
+        return vec![];
+    };
+    let e = match end.map(str::parse::<usize>) {
+        Some(Ok(e)) => Some(e),
+        Some(Err(_)) => return vec![],
+        None => None,
+    };
+
+    let Some(verses) = BIBLE_MAP.get(book) else {
+        return vec![];
+    };
+    if c == 0 || c > verses.len() || v == 0 || v > verses[c - 1] {
+        return vec![];
+    }
+    if e.is_some_and(|e| e <= v || e > verses[c - 1]) {
+        return vec![];
+    }
+
+    // "1 John" → "first john", "one john"
+    let name = base_name(book).to_lowercase();
+    let mut books = match book
+        .split_once(' ')
+        .and_then(|(n, _)| n.parse::<u32>().ok())
+    {
+        Some(n) => ORDINALS
+            .iter()
+            .filter(|(w, d)| d.parse() == Ok(n) && w.chars().all(char::is_alphabetic))
+            .map(|(w, _)| format!("{w} {name}"))
+            .chain([format!("{} {name}", number_to_words(n as usize))])
+            .collect(),
+        None => vec![name.clone()],
+    };
+    if name == "psalm" {
+        books.push("psalms".into());
+    }
+
+    let spellings = |n: usize| {
+        let mut s = vec![number_to_words(n)];
+        s.extend(number_to_digit_words(n));
+        s
+    };
+    let mut verse_phrases = Vec::new();
+    for start in spellings(v) {
+        match e {
+            Some(e) => {
+                for end in spellings(e) {
+                    verse_phrases.push(format!("verses {start} through {end}"));
+                    verse_phrases.push(format!("verses {start} to {end}"));
+                    if e == v + 1 {
+                        verse_phrases.push(format!("verses {start} and {end}"));
+                    }
+                }
+            }
+            None => verse_phrases.push(format!("verse {start}")),
+        }
+    }
+
+    // "john three sixteen" only when the number words split unambiguously
+    let bare_numbers = format!("{} {}", number_to_words(c), number_to_words(v));
+    let bare = number_groups(&bare_numbers).as_deref() == Some(&[c, v]);
+    let bare_end = e.map_or(String::new(), |e| {
+        format!(" through {}", number_to_words(e))
+    });
+
+    let mut forms = Vec::new();
+    for book in &books {
+        for chapter in spellings(c) {
+            for verse in &verse_phrases {
+                forms.push(format!("{book} {chapter} {verse}"));
+                forms.push(format!("{book} chapter {chapter} {verse}"));
+            }
+        }
+        // one-chapter books are usually read without the chapter
+        if verses.len() == 1 {
+            for verse in &verse_phrases {
+                forms.push(format!("{book} {verse}"));
+            }
+        }
+        if bare {
+            forms.push(format!("{book} {bare_numbers}{bare_end}"));
+        }
+    }
+
+    forms
+}
+
 /// Map for converting words to numbers, e.g. "twenty" → "20"
 pub mod word_to_num {
+    use once_cell::sync::Lazy;
     use std::collections::HashMap;
 
+    pub const WORDS: [(&str, u32); 31] = [
+        ("o", 0),
+        ("oh", 0),
+        ("zero", 0),
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+        ("ten", 10),
+        ("eleven", 11),
+        ("twelve", 12),
+        ("thirteen", 13),
+        ("fourteen", 14),
+        ("fifteen", 15),
+        ("sixteen", 16),
+        ("seventeen", 17),
+        ("eighteen", 18),
+        ("nineteen", 19),
+        ("twenty", 20),
+        ("thirty", 30),
+        ("forty", 40),
+        ("fifty", 50),
+        ("sixty", 60),
+        ("seventy", 70),
+        ("eighty", 80),
+        ("ninety", 90),
+        ("hundred", 100),
+    ];
+
+    static NUMS: Lazy<HashMap<&'static str, u32>> = Lazy::new(|| WORDS.iter().cloned().collect());
+
     pub fn parse(tok: &str) -> Result<u32, &'static str> {
-        let nums: HashMap<&str, u32> = [
-            ("o", 0),
-            ("oh", 0),
-            ("zero", 0),
-            ("one", 1),
-            ("two", 2),
-            ("three", 3),
-            ("four", 4),
-            ("five", 5),
-            ("six", 6),
-            ("seven", 7),
-            ("eight", 8),
-            ("nine", 9),
-            ("ten", 10),
-            ("eleven", 11),
-            ("twelve", 12),
-            ("thirteen", 13),
-            ("fourteen", 14),
-            ("fifteen", 15),
-            ("sixteen", 16),
-            ("seventeen", 17),
-            ("eighteen", 18),
-            ("nineteen", 19),
-            ("twenty", 20),
-            ("thirty", 30),
-            ("forty", 40),
-            ("fifty", 50),
-            ("sixty", 60),
-            ("seventy", 70),
-            ("eighty", 80),
-            ("ninety", 90),
-            ("hundred", 100),
-        ]
-        .iter()
-        .cloned()
-        .collect();
-        nums.get(tok).cloned().ok_or("Invalid number word")
+        NUMS.get(tok).cloned().ok_or("Invalid number word")
+    }
+
+    /// The word for a number in `WORDS`, e.g. 20 → "twenty"
+    pub fn word(n: u32) -> Option<&'static str> {
+        WORDS
+            .iter()
+            .filter(|(w, _)| *w != "o")
+            .find(|(_, v)| *v == n)
+            .map(|(w, _)| *w)
     }
 }
 
-#[allow(clippy::collapsible_if)]
-fn word_to_number(token: &str) -> Option<String> {
-    // 1) Normalize casing & hyphens, and bind to keep alive for the borrows below
+/// What a spoken number group can still take, e.g. "twenty" can take a unit
+#[derive(Clone, Copy, PartialEq)]
+enum Slot {
+    /// After a unit: "hundred" or "oh"
+    Scale,
+    /// After "hundred": tens, teens or units
+    Tens,
+    /// After tens: a unit
+    Unit,
+    /// After "one oh": a unit, read as a digit
+    Digit,
+    Closed,
+}
+
+/// Split number words into the numbers they were spoken as:
+/// "three sixteen" → [3, 16], "one hundred nineteen" → [119], "one oh five" → [105]
+fn number_groups(token: &str) -> Option<Vec<usize>> {
     let normalized = token.to_lowercase().replace('-', " ");
-    let parts: Vec<&str> = normalized
-        .split_whitespace()
-        .map(str::trim)
-        .filter(|w| !w.is_empty())
-        .collect();
-    if parts.is_empty() {
+    let mut groups: Vec<(usize, Slot)> = Vec::new();
+
+    for w in normalized.split_whitespace() {
+        if let Ok(n) = w.parse::<usize>() {
+            groups.push((n, Slot::Closed));
+            continue;
+        }
+        let val = word_to_num::parse(w).ok()? as usize;
+        let slot = groups.last().map_or(Slot::Closed, |&(_, s)| s);
+        let last = groups.last_mut();
+        match (val, slot, last) {
+            // "one hundred"
+            (100, Slot::Scale, Some(g)) => *g = (g.0 * 100, Slot::Tens),
+            (100, _, _) => groups.push((100, Slot::Tens)),
+            // "one oh five"
+            (0, Slot::Scale, Some(g)) => *g = (g.0 * 10, Slot::Digit),
+            (0, _, _) => groups.push((0, Slot::Digit)),
+            (1..=9, Slot::Digit, Some(g)) => *g = (g.0 * 10 + val, Slot::Closed),
+            // "one hundred twenty three"
+            (1..=9, Slot::Tens | Slot::Unit, Some(g)) => *g = (g.0 + val, Slot::Closed),
+            (10..=19, Slot::Tens, Some(g)) => *g = (g.0 + val, Slot::Closed),
+            (20..=90, Slot::Tens, Some(g)) => *g = (g.0 + val, Slot::Unit),
+            (1..=9, _, _) => groups.push((val, Slot::Scale)),
+            (10..=19, _, _) => groups.push((val, Slot::Closed)),
+            _ => groups.push((val, Slot::Unit)),
+        }
+    }
+
+    if groups.is_empty() {
         return None;
     }
+    Some(groups.into_iter().map(|(n, _)| n).collect())
+}
 
-    // 2) If it's a raw digit, short-circuit
-    if parts.len() == 1 {
-        if let Ok(n) = parts[0].parse::<usize>() {
-            return Some(n.to_string());
-        }
+/// Read number words as one number. Groups spoken digit-wise are joined,
+/// so "one nineteen" → 119 and "twenty three" → 23.
+pub(crate) fn word_to_number(token: &str) -> Option<usize> {
+    let digits = number_groups(token)?
+        .iter()
+        .map(usize::to_string)
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+/// Spell out a number, e.g. 119 → "one hundred nineteen"
+fn number_to_words(n: usize) -> String {
+    let word = |n: usize| word_to_num::word(n as u32).unwrap_or_default();
+    match n {
+        100.. if n.is_multiple_of(100) => format!("{} hundred", word(n / 100)),
+        100.. => format!("{} hundred {}", word(n / 100), number_to_words(n % 100)),
+        20.. if n.is_multiple_of(10) => word(n).to_string(),
+        20.. => format!("{} {}", word(n / 10 * 10), word(n % 10)),
+        _ => word(n).to_string(),
     }
+}
 
-    // 3) Otherwise do a scale-aware parse:
-    //    total = the running grand total
-    //    current = the subtotal before hitting a scale (e.g. hundred)
-    //
-    let mut total = 0usize;
-    let mut current = 0usize;
-    for &w in &parts {
-        match word_to_num::parse(w) {
-            Ok(100) => {
-                // “hundred” multiplies the current (or 1 if none)
-                if current == 0 {
-                    current = 1;
-                }
-                current *= 100;
-            }
-            Ok(val) => {
-                // units/tens just add
-                current += val as usize;
-            }
-            Err(_) => {
-                // if any token fails, bail
-                return None;
-            }
-        }
+/// Spell out a number digit-wise, e.g. 119 → "one nineteen", 105 → "one oh five"
+fn number_to_digit_words(n: usize) -> Option<String> {
+    let (hundreds, rest) = (n / 100, n % 100);
+    match (hundreds, rest) {
+        (0, _) | (_, 0) => None,
+        (_, 1..=9) => Some(format!(
+            "{} oh {}",
+            number_to_words(hundreds),
+            number_to_words(rest)
+        )),
+        _ => Some(format!(
+            "{} {}",
+            number_to_words(hundreds),
+            number_to_words(rest)
+        )),
     }
-    total += current;
+}
 
-    Some(total.to_string())
+/// Lowercase and collapse whitespace, e.g. "Song  of Solomon" → "song of solomon"
+pub(crate) fn squash(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
 }
 
 #[allow(clippy::collapsible_if)]
 /// Fuzzy-match a candidate book against BIBLE_MAP
-fn fuzzy_book_match(candidate: &str) -> Option<String> {
-    use fuzzy_matcher::FuzzyMatcher;
+fn fuzzy_book_match(candidate: &str, threshold: i64) -> Option<String> {
     use fuzzy_matcher::skim::SkimMatcherV2;
+    use fuzzy_matcher::FuzzyMatcher;
+
+    let candidate = squash(candidate);
+    // Short names like "job" score below the threshold, so take exact hits as-is
+    if BOOK_NAMES.contains(&candidate) {
+        return Some(candidate);
+    }
 
     let matcher = SkimMatcherV2::default();
     let mut best: Option<(&str, i64)> = None;
-    for &book in BIBLE_MAP.keys() {
-        if let Some(score) = matcher.fuzzy_match(&book.to_lowercase(), &candidate.to_lowercase()) {
+    for book in BOOK_NAMES.iter() {
+        if let Some(score) = matcher.fuzzy_match(book, &candidate) {
             if best.is_none_or(|(_, s)| score > s) {
                 best = Some((book, score));
             }
         }
     }
     if let Some((name, score)) = best {
-        if score >= 80 {
-            return Some(name.to_string().to_lowercase());
+        if score >= threshold {
+            return Some(name.to_string());
         }
     }
     None
@@ -425,8 +859,7 @@ mod tests {
             ),
             (
                 "for we celebrate a fresh start in second corinthians five verse seventeen",
-                // vec!["2 Corinthians 5:17"],
-                vec![],
+                vec!["2 Corinthians 5:17"],
             ),
             (
                 "finding the blueprint for peace in philippians four verses six and seven",
@@ -443,4 +876,201 @@ mod tests {
             assert_eq!(got, expected, "Failed on input: {}", input);
         }
     }
+
+    #[test]
+    fn test_detect_config() {
+        let input = "in second corinthian five verse seventeen and john three sixteen";
+        assert_eq!(bible_verse(input), vec!["2 Corinthians 5:17", "John 3:16"]);
+
+        let strict = DetectConfig {
+            fuzzy_threshold: 1000,
+            bare_references: false,
+            ..DetectConfig::default()
+        };
+        assert!(bible_verse_with(input, &strict).is_empty());
+
+        let aliases = DetectConfig {
+            aliases: Aliases::new(&[("collisions", "colossians")]),
+            ..DetectConfig::default()
+        };
+        assert_eq!(
+            bible_verse_with("collisions three verse two", &aliases),
+            vec!["Colossians 3:2"]
+        );
+    }
+
+    #[test]
+    fn test_aliases_match_whole_words() {
+        let aliases = Aliases::new(&[("root", "ruth"), ("Collisions", "colossians")]);
+        assert_eq!(
+            normalize_text("uprooted in root one verse sixteen", &aliases),
+            "uprooted in ruth one verse sixteen"
+        );
+        assert_eq!(
+            normalize_text("collisions three verse two", &aliases),
+            "colossians three verse two"
+        );
+    }
+
+    #[test]
+    fn test_translation_cues() {
+        let cases = vec![
+            (
+                "in the niv it reads john three verse sixteen",
+                vec![("John 3:16", Some("NIV"))],
+            ),
+            (
+                "romans eight verse twenty eight in the amplified",
+                vec![("Romans 8:28", Some("AMP"))],
+            ),
+            (
+                "the n k j v puts psalm twenty three verse one this way",
+                vec![("Psalm 23:1", Some("NKJV"))],
+            ),
+            (
+                "king james says in first john one verse nine but the e s v in john three verse sixteen",
+                vec![("1 John 1:9", Some("KJV")), ("John 3:16", Some("ESV"))],
+            ),
+            ("john three verse sixteen", vec![("John 3:16", None)]),
+            (
+                "i grew up on the niv and we have been through so many seasons since then in this church family together \
+                 so turn to john three verse sixteen",
+                vec![("John 3:16", None)],
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let got = detect(input);
+            let got = got
+                .iter()
+                .map(|d| (d.reference.as_str(), d.translation.as_deref()))
+                .collect::<Vec<_>>();
+            assert_eq!(got, expected, "Failed on input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_session_skips_repeated_partials() {
+        let mut session = Session::default();
+        let feed = |session: &mut Session, text: &str| {
+            session
+                .feed(text)
+                .into_iter()
+                .map(|d| d.reference)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(feed(&mut session, "in john three"), Vec::<String>::new());
+        assert_eq!(
+            feed(&mut session, "in john three verse sixteen"),
+            ["John 3:16"]
+        );
+        assert_eq!(
+            feed(&mut session, "in john three verse sixteen for god"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            feed(&mut session, "and romans six verse twenty three"),
+            ["Romans 6:23"]
+        );
+        assert_eq!(
+            feed(&mut session, "back to john three verse sixteen"),
+            Vec::<String>::new()
+        );
+        assert_eq!(session.history(), ["John 3:16", "Romans 6:23"]);
+    }
+
+    #[test]
+    fn test_session_reports_a_reference_again_in_a_later_utterance() {
+        let mut session = Session::default();
+        let references = |detections: Vec<Detection>| {
+            detections
+                .into_iter()
+                .map(|d| d.reference)
+                .collect::<Vec<_>>()
+        };
+
+        let john = "for god so loved the world in john three verse sixteen";
+        assert_eq!(references(session.feed(john)), ["John 3:16"]);
+        assert!(session.feed_final(john).is_empty());
+
+        let romans = "and romans six verse twenty three";
+        assert_eq!(references(session.feed_final(romans)), ["Romans 6:23"]);
+
+        let back = "back to john three verse sixteen";
+        assert_eq!(references(session.feed(back)), ["John 3:16"]);
+        assert!(session.feed(back).is_empty());
+        assert_eq!(session.history(), ["John 3:16", "Romans 6:23", "John 3:16"]);
+    }
+
+    #[test]
+    fn test_session_skips_every_reference_in_a_repeated_partial() {
+        let mut session = Session::default();
+        let partial = "john three verse sixteen and romans eight verse twenty eight";
+        let references = session
+            .feed(partial)
+            .into_iter()
+            .map(|d| d.reference)
+            .collect::<Vec<_>>();
+        assert_eq!(references, ["John 3:16", "Romans 8:28"]);
+        assert!(session.feed(partial).is_empty());
+        assert!(session.feed(&format!("{partial} for we know")).is_empty());
+    }
+
+    #[test]
+    fn test_spoken_forms() {
+        let forms = spoken_forms("1 John 1:9");
+        assert!(forms.contains(&"first john one verse nine".to_string()));
+        assert!(forms.contains(&"one john one nine".to_string()));
+
+        let forms = spoken_forms("Psalm 119:105");
+        assert!(forms.contains(&"psalm one hundred nineteen verse one oh five".to_string()));
+        assert!(forms.contains(&"psalm one nineteen verse one hundred five".to_string()));
+
+        // "john twenty one" could be John 21 or John 20:1
+        assert!(!spoken_forms("John 20:1").contains(&"john twenty one".to_string()));
+
+        assert!(spoken_forms("John 22:1").is_empty());
+        assert!(spoken_forms("NotABook 1:1").is_empty());
+    }
+
+    #[test]
+    fn test_spoken_forms_round_trip() {
+        // KJV versification: 1,189 chapters, 31,102 verses
+        assert_eq!(BIBLE_MAP.len(), 66);
+        assert_eq!(BIBLE_MAP.values().map(Vec::len).sum::<usize>(), 1189);
+        assert_eq!(BIBLE_MAP.values().flatten().sum::<usize>(), 31102);
+
+        let mut failures = Vec::new();
+        for (book, chapters) in BIBLE_MAP.iter() {
+            for (c, &count) in chapters.iter().enumerate() {
+                let mut references = (1..=count)
+                    .map(|v| format!("{book} {}:{v}", c + 1))
+                    .collect::<Vec<_>>();
+                if count > 1 {
+                    references.push(format!("{book} {}:1-{count}", c + 1));
+                }
+
+                for reference in references {
+                    let forms = spoken_forms(&reference);
+                    if forms.is_empty() {
+                        failures.push((reference.clone(), String::new(), vec![]));
+                    }
+                    for spoken in forms {
+                        let got = bible_verse(&spoken);
+                        if got != [reference.as_str()] {
+                            failures.push((reference.clone(), spoken, got));
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "{} round-trip failures, e.g. {:?}",
+            failures.len(),
+            &failures[..failures.len().min(10)]
+        );
+    }
 }
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+/// One labelled line of a transcript: `{"text": "...", "expected": ["John 3:16"]}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sample {
+    pub text: String,
+    #[serde(default)]
+    pub expected: Vec<String>,
+}
+
+/// A sample plus where it came from, e.g. "easter.jsonl:12"
+#[derive(Debug, Clone)]
+pub struct Labelled {
+    pub source: String,
+    pub sample: Sample,
+}
+
+/// A reference the detector missed or made up
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confusion {
+    pub source: String,
+    pub text: String,
+    pub reference: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub samples: usize,
+    pub true_positives: usize,
+    pub missed: Vec<Confusion>,
+    pub spurious: Vec<Confusion>,
+}
+
+impl Report {
+    pub fn precision(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.spurious.len(),
+        )
+    }
+
+    pub fn recall(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.missed.len())
+    }
+
+    pub fn f1(&self) -> f64 {
+        let (p, r) = (self.precision(), self.recall());
+        if p + r == 0.0 {
+            0.0
+        } else {
+            2.0 * p * r / (p + r)
+        }
+    }
+}
+
+fn ratio(n: usize, d: usize) -> f64 {
+    if d == 0 {
+        1.0
+    } else {
+        n as f64 / d as f64
+    }
+}
+
+/// Load every `*.jsonl` file in a directory, skipping blank lines
+pub fn load_corpus(dir: &Path) -> Result<Vec<Labelled>> {
+    let mut files = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut corpus = Vec::new();
+    for path in files {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let data = fs::read_to_string(&path)?;
+        for (i, line) in data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let sample: Sample = serde_json::from_str(line)
+                .with_context(|| format!("{name}:{}: invalid sample", i + 1))?;
+            corpus.push(Labelled {
+                source: format!("{name}:{}", i + 1),
+                sample,
+            });
+        }
+    }
+
+    Ok(corpus)
+}
+
+/// Score a detector against a corpus. References are compared as
+/// multisets, so detecting "John 3:16" twice for one mention is spurious.
+pub fn evaluate<F>(corpus: &[Labelled], detector: F) -> Report
+where
+    F: Fn(&str) -> Vec<String>,
+{
+    let mut report = Report::default();
+
+    for item in corpus {
+        report.samples += 1;
+        let mut expected = item.sample.expected.clone();
+
+        for reference in detector(&item.sample.text) {
+            match expected.iter().position(|e| *e == reference) {
+                Some(i) => {
+                    expected.remove(i);
+                    report.true_positives += 1;
+                }
+                None => report.spurious.push(Confusion {
+                    source: item.source.clone(),
+                    text: item.sample.text.clone(),
+                    reference,
+                }),
+            }
+        }
+
+        for reference in expected {
+            report.missed.push(Confusion {
+                source: item.source.clone(),
+                text: item.sample.text.clone(),
+                reference,
+            });
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labelled(text: &str, expected: &[&str]) -> Labelled {
+        Labelled {
+            source: "test.jsonl:1".into(),
+            sample: Sample {
+                text: text.into(),
+                expected: expected.iter().map(|e| e.to_string()).collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_evaluate_counts_missed_and_spurious() {
+        let corpus = vec![
+            labelled("john three verse sixteen", &["John 3:16"]),
+            labelled("romans six verse twenty three", &["Romans 6:23"]),
+            labelled("no scripture here", &[]),
+        ];
+
+        let report = evaluate(&corpus, |text| match text {
+            "john three verse sixteen" => vec!["John 3:16".into()],
+            "no scripture here" => vec!["Mark 1:1".into()],
+            _ => vec![],
+        });
+
+        assert_eq!(report.samples, 3);
+        assert_eq!(report.true_positives, 1);
+        assert_eq!(report.missed[0].reference, "Romans 6:23");
+        assert_eq!(report.spurious[0].reference, "Mark 1:1");
+        assert_eq!(report.precision(), 0.5);
+        assert_eq!(report.recall(), 0.5);
+        assert_eq!(report.f1(), 0.5);
+    }
+
+    #[test]
+    fn test_load_corpus() {
+        let corpus = load_corpus(Path::new("transcripts")).unwrap();
+        assert!(!corpus.is_empty());
+        assert!(corpus[0].source.ends_with(".jsonl:1"));
+
+        let report = evaluate(&corpus, crate::detect::bible_verse);
+        assert!(report.recall() > 0.5, "{:?}", report.missed);
+    }
+}
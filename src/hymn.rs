@@ -0,0 +1,179 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use std::fs;
+
+use crate::detect::{squash, word_to_number, NUMBERS_PATTERN};
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Hymn {
+    pub number: u32,
+    pub title: String,
+    pub first_line: String,
+    /// Page in the printed hymnal, when it differs from the number
+    #[serde(default)]
+    pub page: Option<u32>,
+}
+
+impl Hymn {
+    /// "Hymn 245: Amazing Grace"
+    pub fn heading(&self) -> String {
+        format!("Hymn {}: {}", self.number, self.title)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Hymnal {
+    pub name: String,
+    pub hymns: Vec<Hymn>,
+}
+
+impl Hymnal {
+    pub fn by_number(&self, number: u32) -> Option<&Hymn> {
+        self.hymns.iter().find(|h| h.number == number)
+    }
+
+    pub fn by_page(&self, page: u32) -> Option<&Hymn> {
+        self.hymns
+            .iter()
+            .find(|h| h.page == Some(page))
+            .or_else(|| self.by_number(page))
+    }
+}
+
+pub fn load_hymnal(path: &str) -> Result<Hymnal> {
+    let data = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+/// "hymn number two forty five", "song 12"
+static HYMN_RE: Lazy<Regex> = Lazy::new(|| {
+    let nums = NUMBERS_PATTERN.as_str();
+    let pat = format!(
+        r"(?ix)
+        \b(?:hymn|song)                      # announcement keyword
+        (?:\s+(?:number|no\.?))?             # optional 'number'
+        \s+({nums})                          # hymn number (words or digits)
+        \b"
+    );
+    Regex::new(&pat).unwrap()
+});
+
+/// "page three hundred twelve in the hymnal", "number five in your hymnal"
+static HYMNAL_RE: Lazy<Regex> = Lazy::new(|| {
+    let nums = NUMBERS_PATTERN.as_str();
+    let pat = format!(
+        r"(?ix)
+        \b(page|number)\s+({nums})           # page or number
+        \s+in\s+(?:the|your|our)\s+          # in the
+        (?:hymnal|hymn\s*book|song\s*book)   # hymnal
+        \b"
+    );
+    Regex::new(&pat).unwrap()
+});
+
+/// An announcement cue a few words before a title or first line: "let us
+/// sing ...", "turn to ...", "the hymn ..."
+static CUE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:sing|hymn|song|turn\s+to|open\s+to)(?:\s+\w+){0,3}\s*$").unwrap()
+});
+
+static PUNCTUATION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[^\w\s-]").unwrap());
+
+/// Lowercase words only, e.g. "Amazing Grace! how sweet" → "amazing grace how sweet"
+fn plain(text: &str) -> String {
+    squash(&PUNCTUATION_RE.replace_all(text, ""))
+}
+
+/// Whole-word position of `phrase` in `text`
+fn find_phrase(text: &str, phrase: &str) -> Option<usize> {
+    if phrase.is_empty() {
+        return None;
+    }
+    let padded = format!(" {text} ");
+    padded.find(&format!(" {phrase} "))
+}
+
+/// Extract hymn and song announcements from an input text, by number, page,
+/// or a title or first line that follows an announcement cue
+pub fn hymn_announcement<'a>(input: &str, hymnal: &'a Hymnal) -> Vec<&'a Hymn> {
+    let text = plain(input);
+    let mut found: Vec<(usize, &Hymn)> = Vec::new();
+
+    for cap in HYMN_RE.captures_iter(&text) {
+        let Some(n) = word_to_number(&cap[1]) else {
+            continue;
+        };
+        if let Some(hymn) = u32::try_from(n).ok().and_then(|n| hymnal.by_number(n)) {
+            found.push((cap.get(0).unwrap().start(), hymn));
+        }
+    }
+
+    for cap in HYMNAL_RE.captures_iter(&text) {
+        let Some(n) = word_to_number(&cap[2]).and_then(|n| u32::try_from(n).ok()) else {
+            continue;
+        };
+        let hymn = match &cap[1].to_lowercase()[..] {
+            "page" => hymnal.by_page(n),
+            _ => hymnal.by_number(n),
+        };
+        if let Some(hymn) = hymn {
+            found.push((cap.get(0).unwrap().start(), hymn));
+        }
+    }
+
+    for hymn in &hymnal.hymns {
+        let at = find_phrase(&text, &plain(&hymn.title))
+            .or_else(|| find_phrase(&text, &plain(&hymn.first_line)));
+        // "by the amazing grace of god" is preaching, not an announcement
+        if let Some(at) = at.filter(|&at| CUE_RE.is_match(&text[..at])) {
+            found.push((at, hymn));
+        }
+    }
+
+    found.sort_by_key(|(at, _)| *at);
+    let mut results: Vec<&Hymn> = Vec::new();
+    for (_, hymn) in found {
+        if !results.iter().any(|h| h.number == hymn.number) {
+            results.push(hymn);
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hymn_detection() {
+        let hymnal = load_hymnal("hymnals/test_hymnal.json").unwrap();
+
+        let cases = vec![
+            (
+                "please stand as we sing hymn number two forty five",
+                vec![245],
+            ),
+            ("turn to page three hundred twelve in the hymnal", vec![280]),
+            ("number seventy in your hymnal", vec![70]),
+            ("song 12 and then hymn one oh four", vec![12, 104]),
+            ("let us sing amazing grace together", vec![245]),
+            ("now turn to blessed assurance jesus is mine", vec![70]),
+            ("blessed assurance jesus is mine", vec![]),
+            ("we are saved by the amazing grace of god", vec![]),
+            ("christ the rock of ages was cleft for us", vec![]),
+            ("hymn number two forty five amazing grace", vec![245]),
+            ("we had two hundred forty five people in church", vec![]),
+            ("hymn number nine hundred", vec![]),
+        ];
+
+        for (input, expected) in cases {
+            let got = hymn_announcement(input, &hymnal)
+                .iter()
+                .map(|h| h.number)
+                .collect::<Vec<_>>();
+            assert_eq!(got, expected, "Failed on input: {}", input);
+        }
+    }
+}
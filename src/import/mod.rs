@@ -0,0 +1,383 @@
+//! Importers that turn OSIS, USFM, USX and Zefania Bibles into the
+//! translations/<abbr>/<abbr>.json format read by `BibleStore`
+
+mod osis;
+mod usfm;
+mod usx;
+mod zefania;
+
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::bible::{BibleJson, JsonBook, JsonChapter, JsonVerse, Segment, CANON};
+use crate::translations::TranslationMeta;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Osis,
+    Usfm,
+    Usx,
+    Zefania,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "osis" => Ok(Format::Osis),
+            "usfm" | "sfm" => Ok(Format::Usfm),
+            "usx" => Ok(Format::Usx),
+            "zefania" => Ok(Format::Zefania),
+            _ => bail!("Unknown format: {s} (expected osis, usfm, usx or zefania)"),
+        }
+    }
+}
+
+/// A parsed Bible plus whatever metadata the source carries
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub meta: TranslationMeta,
+    pub bible: BibleJson,
+}
+
+impl Imported {
+    pub fn verse_count(&self) -> usize {
+        self.bible
+            .books
+            .iter()
+            .flat_map(|b| &b.chapters)
+            .map(|c| c.verses.len())
+            .sum()
+    }
+}
+
+/// Guess the format from the extension, then the root element
+fn detect_format(path: &Path, sample: &str) -> Option<Format> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    match ext.as_str() {
+        "usfm" | "sfm" | "ptx" => return Some(Format::Usfm),
+        "usx" => return Some(Format::Usx),
+        _ => {}
+    }
+    let lower = sample.to_lowercase();
+    if lower.contains("<osis") {
+        Some(Format::Osis)
+    } else if lower.contains("<xmlbible") {
+        Some(Format::Zefania)
+    } else if lower.contains("<usx") {
+        Some(Format::Usx)
+    } else if sample.trim_start().starts_with("\\id ") {
+        Some(Format::Usfm)
+    } else {
+        None
+    }
+}
+
+/// Source files: the file itself, or every file in a directory (USFM and USX
+/// usually come one book per file)
+fn source_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+/// Import a Bible file (or a directory of book files)
+pub fn import(path: &Path, format: Option<Format>) -> Result<Imported> {
+    let mut builder = Builder::default();
+    let mut meta = TranslationMeta::default();
+
+    for file in source_files(path)? {
+        let src = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let Some(format) = format.or_else(|| detect_format(&file, &src)) else {
+            continue;
+        };
+        match format {
+            Format::Osis => osis::read(&src, &mut builder, &mut meta),
+            Format::Usfm => usfm::read(&src, &mut builder, &mut meta),
+            Format::Usx => usx::read(&src, &mut builder, &mut meta),
+            Format::Zefania => zefania::read(&src, &mut builder, &mut meta),
+        }
+        .with_context(|| format!("Failed to import {}", file.display()))?;
+    }
+
+    let bible = builder.finish();
+    if bible.books.is_empty() {
+        bail!("No verses found in {}", path.display());
+    }
+    Ok(Imported { meta, bible })
+}
+
+/// Write `translations/<abbr>/<abbr>.json` and its metadata.json
+pub fn install(imported: &Imported, dir: &Path) -> Result<PathBuf> {
+    let id = imported.meta.abbreviation.to_lowercase();
+    if id.is_empty() {
+        bail!("An abbreviation is required to install a translation");
+    }
+    let target = dir.join(&id);
+    fs::create_dir_all(&target)?;
+
+    let path = target.join(format!("{id}.json"));
+    fs::write(&path, serde_json::to_string(&imported.bible)?)?;
+    fs::write(
+        target.join("metadata.json"),
+        serde_json::to_string_pretty(&imported.meta)?,
+    )?;
+    Ok(path)
+}
+
+/// Index into `CANON` for an OSIS id ("Matt") or USFM code ("MAT")
+fn canon_index(id: &str) -> Option<usize> {
+    CANON
+        .iter()
+        .position(|(_, osis, usfm)| *osis == id || usfm.eq_ignore_ascii_case(id))
+}
+
+/// Leading digits of "16", "16-17" or "16a"
+fn leading_number(s: &str) -> Option<u32> {
+    let digits = s
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Style {
+    words_of_christ: bool,
+    italic: bool,
+}
+
+/// chapter → verse → styled runs of text
+type Chapters = BTreeMap<u32, BTreeMap<u32, Vec<(String, Style)>>>;
+
+/// Collects verse text from any of the parsers, in whatever order the source
+/// uses, then lays it out in canonical order
+#[derive(Debug, Default)]
+struct Builder {
+    books: BTreeMap<usize, Chapters>,
+    /// Verses that start a paragraph
+    paragraphs: BTreeSet<(usize, u32, u32)>,
+    current: Option<(usize, u32, u32)>,
+    pending_paragraph: bool,
+}
+
+impl Builder {
+    fn start_verse(&mut self, book: usize, chapter: u32, verse: u32) {
+        self.current = Some((book, chapter, verse));
+        if std::mem::take(&mut self.pending_paragraph) {
+            self.paragraphs.insert((book, chapter, verse));
+        }
+        self.books
+            .entry(book)
+            .or_default()
+            .entry(chapter)
+            .or_default()
+            .entry(verse)
+            .or_default();
+    }
+
+    fn end_verse(&mut self) {
+        self.current = None;
+    }
+
+    /// A paragraph starts here; it is kept when a verse follows directly
+    fn paragraph(&mut self) {
+        self.pending_paragraph = true;
+    }
+
+    fn text(&mut self, text: &str, style: Style) {
+        let Some((b, c, v)) = self.current else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+        if !text.trim().is_empty() {
+            // A break in the middle of a verse is not kept
+            self.pending_paragraph = false;
+        }
+        let runs = self
+            .books
+            .get_mut(&b)
+            .and_then(|b| b.get_mut(&c))
+            .and_then(|c| c.get_mut(&v))
+            .expect("verse was started");
+        match runs.last_mut() {
+            Some((last, s)) if *s == style => last.push_str(text),
+            _ => runs.push((text.to_string(), style)),
+        }
+    }
+
+    fn finish(self) -> BibleJson {
+        let mut bible = BibleJson::default();
+        for (b, chapters) in self.books {
+            let mut book = JsonBook {
+                name: CANON[b].0.to_string(),
+                chapters: Vec::new(),
+            };
+            for (c, verses) in chapters {
+                let mut chapter = JsonChapter {
+                    chapter: c,
+                    verses: Vec::new(),
+                };
+                for (v, runs) in verses {
+                    let segments = collapse(&runs);
+                    if segments.is_empty() {
+                        continue;
+                    }
+                    let text = segments.iter().map(|s| s.text.as_str()).collect();
+                    let plain = segments.iter().all(|s| !s.words_of_christ && !s.italic);
+                    chapter.verses.push(JsonVerse {
+                        verse: v,
+                        text,
+                        paragraph: self.paragraphs.contains(&(b, c, v)),
+                        segments: if plain { Vec::new() } else { segments },
+                    });
+                }
+                if !chapter.verses.is_empty() {
+                    book.chapters.push(chapter);
+                }
+            }
+            if !book.chapters.is_empty() {
+                bible.books.push(book);
+            }
+        }
+        bible
+    }
+}
+
+/// Collapse whitespace across runs (a space joins the run before it), drop
+/// empty runs and merge neighbours with the same style
+fn collapse(runs: &[(String, Style)]) -> Vec<Segment> {
+    let mut out: Vec<(String, Style)> = Vec::new();
+    let mut pending_space = false;
+
+    for (text, style) in runs {
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                pending_space = true;
+                continue;
+            }
+            if std::mem::take(&mut pending_space) {
+                if let Some((last, _)) = out.iter_mut().rev().find(|(t, _)| !t.is_empty()) {
+                    last.push(' ');
+                }
+            }
+            match out.last_mut() {
+                Some((last, s)) if s == style => last.push(ch),
+                _ => out.push((ch.to_string(), *style)),
+            }
+        }
+    }
+
+    let mut segments: Vec<Segment> = Vec::new();
+    for (text, style) in out {
+        match segments.last_mut() {
+            Some(last)
+                if last.words_of_christ == style.words_of_christ && last.italic == style.italic =>
+            {
+                last.text.push_str(&text)
+            }
+            _ => segments.push(Segment {
+                text,
+                words_of_christ: style.words_of_christ,
+                italic: style.italic,
+            }),
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// John 3:16-17 (red letter) and 1 John 2:23 (supplied words), as every
+    /// fixture in testdata/import should come out
+    pub(super) fn assert_fixture(imported: &Imported) {
+        let john = &imported.bible.books[0];
+        assert_eq!(john.name, "John");
+        assert_eq!(john.chapters[0].chapter, 3);
+        let verses = &john.chapters[0].verses;
+        assert_eq!(verses.len(), 2);
+
+        assert_eq!(verses[0].verse, 16);
+        assert_eq!(
+            verses[0].text,
+            "For God so loved the world, that he gave his only begotten Son, \
+             that whosoever believeth in him should not perish, but have everlasting life."
+        );
+        assert!(verses[0].paragraph);
+        assert_eq!(verses[0].segments.len(), 1);
+        assert!(verses[0].segments[0].words_of_christ);
+
+        assert_eq!(
+            verses[1].text,
+            "For God sent not his Son into the world to condemn the world; \
+             but that the world through him might be saved."
+        );
+        assert!(!verses[1].paragraph);
+
+        let first_john = &imported.bible.books[1];
+        assert_eq!(first_john.name, "1 John");
+        let verse = &first_john.chapters[0].verses[0];
+        assert_eq!((first_john.chapters[0].chapter, verse.verse), (2, 23));
+        assert_eq!(
+            verse.text,
+            "Whosoever denieth the Son, the same hath not the Father: \
+             but he that acknowledgeth the Son hath the Father also."
+        );
+        assert!(verse.segments[1].italic);
+        assert_eq!(
+            verse.segments[1].text,
+            "but he that acknowledgeth the Son hath the Father also"
+        );
+    }
+
+    #[test]
+    fn test_collapse_whitespace_across_styles() {
+        let plain = Style::default();
+        let italic = Style {
+            italic: true,
+            ..Style::default()
+        };
+        let segments = collapse(&[
+            ("\n  And God said,  ".into(), plain),
+            (" it was ".into(), italic),
+            ("\n good. ".into(), plain),
+        ]);
+        let text = segments.iter().map(|s| s.text.as_str()).collect::<String>();
+        assert_eq!(text, "And God said, it was good.");
+        assert_eq!(segments[1].text, "it was ");
+        assert!(segments[1].italic);
+    }
+
+    #[test]
+    fn test_install_round_trips_through_the_registry() {
+        let mut imported = import(Path::new("testdata/import/kjv.osis.xml"), None).unwrap();
+        imported.meta.abbreviation = "TST".into();
+
+        let dir = std::env::temp_dir().join(format!("easy_sermon_import_{}", std::process::id()));
+        install(&imported, &dir).unwrap();
+
+        let registry = crate::translations::TranslationRegistry::scan(&dir).unwrap();
+        assert_eq!(registry.meta("tst").unwrap().name, "King James Version");
+        let text = registry.get("TST").unwrap().passage("John 3:17");
+        assert!(text.ends_with("might be saved."), "{text}");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use roxmltree::{Document, Node, ParsingOptions};
+
+use super::{canon_index, leading_number, Builder, Style};
+use crate::translations::TranslationMeta;
+
+/// "John.3.16" (or the first of "John.3.16 John.3.17") → (book, chapter, verse)
+fn parse_id(id: &str) -> Option<(usize, u32, u32)> {
+    let first = id.split_whitespace().next()?;
+    let mut parts = first.split('.');
+    let book = canon_index(parts.next()?)?;
+    let chapter = leading_number(parts.next()?)?;
+    let verse = leading_number(parts.next()?)?;
+    Some((book, chapter, verse))
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|c| c.has_tag_name(name))
+        .and_then(|c| c.text())
+        .map(|t| t.trim().to_string())
+}
+
+/// Verses may be containers (`<verse osisID>text</verse>`) or milestones
+/// (`<verse sID/>text<verse eID/>`), and so may words of Christ
+struct Walker<'b> {
+    builder: &'b mut Builder,
+    /// Inside a `<q who="Jesus" sID/>` … `<q eID/>` milestone pair
+    jesus: bool,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, node: Node, style: Style) {
+        for child in node.children() {
+            if child.is_text() {
+                let style = Style {
+                    words_of_christ: style.words_of_christ || self.jesus,
+                    ..style
+                };
+                self.builder.text(child.text().unwrap_or_default(), style);
+                continue;
+            }
+            if !child.is_element() {
+                continue;
+            }
+
+            match child.tag_name().name() {
+                "header" | "note" | "title" | "rdg" | "figure" => {}
+                "verse" => {
+                    if child.has_attribute("eID") {
+                        self.builder.end_verse();
+                        continue;
+                    }
+                    let id = child.attribute("osisID").or(child.attribute("sID"));
+                    if let Some((b, c, v)) = id.and_then(parse_id) {
+                        self.builder.start_verse(b, c, v);
+                    }
+                    if !child.has_attribute("sID") {
+                        self.walk(child, style);
+                        self.builder.end_verse();
+                    }
+                }
+                "chapter" if child.has_attribute("eID") => self.builder.end_verse(),
+                "p" => {
+                    self.builder.paragraph();
+                    self.walk(child, style);
+                }
+                "milestone" if child.attribute("type") == Some("x-p") => self.builder.paragraph(),
+                "q" if child.attribute("who") == Some("Jesus") => {
+                    if child.has_attribute("sID") {
+                        self.jesus = true;
+                    } else if child.has_attribute("eID") {
+                        self.jesus = false;
+                    } else {
+                        let style = Style {
+                            words_of_christ: true,
+                            ..style
+                        };
+                        self.walk(child, style);
+                    }
+                }
+                "transChange" if child.attribute("type") == Some("added") => self.walk(
+                    child,
+                    Style {
+                        italic: true,
+                        ..style
+                    },
+                ),
+                "hi" if child.attribute("type") == Some("italic") => self.walk(
+                    child,
+                    Style {
+                        italic: true,
+                        ..style
+                    },
+                ),
+                _ => self.walk(child, style),
+            }
+        }
+    }
+}
+
+pub(super) fn read(src: &str, builder: &mut Builder, meta: &mut TranslationMeta) -> Result<()> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let doc = Document::parse_with_options(src, options)?;
+
+    if let Some(work) = doc.descendants().find(|n| n.has_tag_name("work")) {
+        if let Some(abbreviation) = work.attribute("osisWork") {
+            meta.abbreviation = abbreviation.to_string();
+        }
+        meta.name = child_text(work, "title").unwrap_or_default();
+        meta.language = child_text(work, "language").unwrap_or_default();
+        meta.copyright = child_text(work, "rights");
+    }
+
+    let mut walker = Walker {
+        builder,
+        jesus: false,
+    };
+    walker.walk(doc.root_element(), Style::default());
+    walker.builder.end_verse();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{import, tests::assert_fixture, Format};
+    use std::path::Path;
+
+    #[test]
+    fn test_osis_import() {
+        let imported = import(Path::new("testdata/import/kjv.osis.xml"), None).unwrap();
+        assert_eq!(imported.meta.abbreviation, "KJV");
+        assert_eq!(imported.meta.name, "King James Version");
+        assert_eq!(imported.meta.copyright.as_deref(), Some("Public Domain"));
+        assert_fixture(&imported);
+
+        let forced = import(
+            Path::new("testdata/import/kjv.osis.xml"),
+            Some(Format::Osis),
+        );
+        assert_eq!(forced.unwrap().verse_count(), 3);
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::usx::starts_paragraph;
+use super::{canon_index, leading_number, Builder, Style};
+use crate::translations::TranslationMeta;
+
+/// `\v`, `\wj*`, `\+add`, `\toc1`, `\qt-s` …
+static MARKER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\(\+?[A-Za-z]+\d*(?:-[se])?\*?)").unwrap());
+
+/// Headings, titles and introductions: the rest of their line is not verse text
+fn skips_line(marker: &str) -> bool {
+    let numbered = |prefix: &str| {
+        marker
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.chars().all(|c| c.is_ascii_digit()))
+    };
+    matches!(
+        marker,
+        "h" | "mr"
+            | "sr"
+            | "r"
+            | "d"
+            | "sp"
+            | "rem"
+            | "sts"
+            | "ide"
+            | "restore"
+            | "cl"
+            | "cd"
+            | "usfm"
+    ) || ["toc", "toca", "mt", "mte", "ms", "s", "sd"]
+        .iter()
+        .any(|p| numbered(p))
+        || (marker.starts_with('i') && marker != "it")
+}
+
+/// Notes run until their closing marker, e.g. `\f … \f*`
+fn note_end(marker: &str) -> Option<&'static str> {
+    match marker {
+        "f" => Some("f*"),
+        "fe" => Some("fe*"),
+        "x" => Some("x*"),
+        "ef" => Some("ef*"),
+        "ex" => Some("ex*"),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Expect {
+    Text,
+    Id,
+    Chapter,
+    Verse,
+    SkipLine,
+}
+
+/// Split "16 For God" into ("16", " For God")
+fn first_word(chunk: &str) -> (&str, &str) {
+    let chunk = chunk.trim_start();
+    let end = chunk.find(char::is_whitespace).unwrap_or(chunk.len());
+    (&chunk[..end], &chunk[end..])
+}
+
+struct Reader<'b> {
+    builder: &'b mut Builder,
+    book: Option<usize>,
+    chapter: u32,
+    style: Style,
+    in_word: bool,
+    expect: Expect,
+    note: Option<&'static str>,
+}
+
+impl Reader<'_> {
+    fn chunk(&mut self, chunk: &str, meta: &mut TranslationMeta) {
+        if self.note.is_some() {
+            return;
+        }
+        let mut text = chunk;
+        match std::mem::replace(&mut self.expect, Expect::Text) {
+            Expect::Text => {}
+            Expect::Id => {
+                let (code, rest) = first_word(chunk);
+                self.book = canon_index(code);
+                let (line, after) = rest.split_once('\n').unwrap_or((rest, ""));
+                if meta.name.is_empty() && !line.trim().is_empty() {
+                    meta.name = line.trim().to_string();
+                }
+                text = after;
+            }
+            Expect::Chapter => {
+                let (n, rest) = first_word(chunk);
+                if let Some(n) = leading_number(n) {
+                    self.chapter = n;
+                }
+                text = rest;
+            }
+            Expect::Verse => {
+                let (n, rest) = first_word(chunk);
+                if let (Some(book), Some(v)) = (self.book, leading_number(n)) {
+                    self.builder.start_verse(book, self.chapter, v);
+                }
+                text = rest;
+            }
+            Expect::SkipLine => match chunk.split_once('\n') {
+                Some((_, after)) => text = after,
+                None => return,
+            },
+        }
+
+        if self.in_word {
+            // \w grace|strong="G5485"\w*
+            text = text.split('|').next().unwrap_or_default();
+        }
+        self.builder.text(text, self.style);
+    }
+
+    fn marker(&mut self, marker: &str) {
+        if let Some(end) = self.note {
+            if marker == end {
+                self.note = None;
+            }
+            return;
+        }
+        let marker = marker.trim_start_matches('+');
+        let (name, closing) = match marker.strip_suffix('*') {
+            Some(name) => (name, true),
+            None => (marker, false),
+        };
+
+        match name {
+            "id" => {
+                self.builder.end_verse();
+                self.expect = Expect::Id;
+            }
+            "c" => {
+                self.builder.end_verse();
+                self.expect = Expect::Chapter;
+            }
+            "v" => self.expect = Expect::Verse,
+            "wj" => self.style.words_of_christ = !closing,
+            "add" => self.style.italic = !closing,
+            "w" => self.in_word = !closing,
+            _ if closing => {}
+            _ => {
+                if let Some(end) = note_end(name) {
+                    self.note = Some(end);
+                } else if skips_line(name) {
+                    self.expect = Expect::SkipLine;
+                } else if starts_paragraph(name) {
+                    self.builder.paragraph();
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn read(src: &str, builder: &mut Builder, meta: &mut TranslationMeta) -> Result<()> {
+    let mut reader = Reader {
+        builder,
+        book: None,
+        chapter: 0,
+        style: Style::default(),
+        in_word: false,
+        expect: Expect::Text,
+        note: None,
+    };
+
+    let mut last = 0;
+    for m in MARKER_RE.captures_iter(src) {
+        let whole = m.get(0).unwrap();
+        reader.chunk(&src[last..whole.start()], meta);
+        reader.marker(&m[1]);
+        last = whole.end();
+    }
+    reader.chunk(&src[last..], meta);
+    reader.builder.end_verse();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{import, tests::assert_fixture};
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_usfm_import() {
+        let imported = import(Path::new("testdata/import/usfm"), None).unwrap();
+        assert_eq!(imported.meta.name, "King James Version");
+        assert_fixture(&imported);
+
+        assert!(skips_line("s1") && skips_line("toc2") && skips_line("ip"));
+        assert!(!skips_line("it") && !skips_line("p") && !skips_line("q1"));
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use roxmltree::{Document, Node, ParsingOptions};
+
+use super::{canon_index, leading_number, Builder, Style};
+use crate::translations::TranslationMeta;
+
+/// Paragraph styles that hold verse text; the rest are headings, titles
+/// and introductions
+pub(super) fn is_body(style: &str) -> bool {
+    matches!(
+        style,
+        "p" | "m"
+            | "po"
+            | "pr"
+            | "cls"
+            | "pmo"
+            | "pm"
+            | "pmc"
+            | "pmr"
+            | "mi"
+            | "nb"
+            | "pc"
+            | "b"
+            | "qr"
+            | "qc"
+    ) || ["pi", "ph", "q", "li", "lim"]
+        .iter()
+        .any(|p| style.starts_with(p) && style[p.len()..].chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Body styles that start a new paragraph (poetry lines and `nb` do not)
+pub(super) fn starts_paragraph(style: &str) -> bool {
+    is_body(style)
+        && !matches!(style, "nb" | "b")
+        && !style.starts_with('q')
+        && !style.starts_with("li")
+}
+
+struct Walker<'b> {
+    builder: &'b mut Builder,
+    book: Option<usize>,
+    chapter: u32,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, node: Node, style: Style) {
+        for child in node.children() {
+            if child.is_text() {
+                self.builder.text(child.text().unwrap_or_default(), style);
+                continue;
+            }
+            if !child.is_element() {
+                continue;
+            }
+
+            match child.tag_name().name() {
+                "book" => {
+                    self.builder.end_verse();
+                    self.book = child.attribute("code").and_then(canon_index);
+                }
+                "chapter" => {
+                    self.builder.end_verse();
+                    if let Some(n) = child.attribute("number").and_then(leading_number) {
+                        self.chapter = n;
+                    }
+                }
+                "verse" => {
+                    if child.has_attribute("eid") {
+                        self.builder.end_verse();
+                    } else if let (Some(book), Some(verse)) = (
+                        self.book,
+                        child.attribute("number").and_then(leading_number),
+                    ) {
+                        self.builder.start_verse(book, self.chapter, verse);
+                    }
+                }
+                "para" => {
+                    let para = child.attribute("style").unwrap_or("p");
+                    if !is_body(para) {
+                        continue;
+                    }
+                    if starts_paragraph(para) {
+                        self.builder.paragraph();
+                    }
+                    self.walk(child, style);
+                }
+                "char" => {
+                    let style = match child.attribute("style") {
+                        Some("wj") => Style {
+                            words_of_christ: true,
+                            ..style
+                        },
+                        Some("add") => Style {
+                            italic: true,
+                            ..style
+                        },
+                        _ => style,
+                    };
+                    self.walk(child, style);
+                }
+                "note" | "figure" | "sidebar" => {}
+                _ => self.walk(child, style),
+            }
+        }
+    }
+}
+
+pub(super) fn read(src: &str, builder: &mut Builder, meta: &mut TranslationMeta) -> Result<()> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let doc = Document::parse_with_options(src, options)?;
+
+    // <book code="JHN">King James Version</book>
+    if meta.name.is_empty() {
+        if let Some(name) = doc
+            .descendants()
+            .find(|n| n.has_tag_name("book"))
+            .and_then(|n| n.text())
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            meta.name = name.to_string();
+        }
+    }
+
+    let mut walker = Walker {
+        builder,
+        book: None,
+        chapter: 0,
+    };
+    walker.walk(doc.root_element(), Style::default());
+    walker.builder.end_verse();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{import, tests::assert_fixture};
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_usx_import() {
+        let imported = import(Path::new("testdata/import/kjv.usx"), None).unwrap();
+        assert_eq!(imported.meta.name, "King James Version");
+        assert_fixture(&imported);
+
+        assert!(is_body("q2") && !starts_paragraph("q2"));
+        assert!(is_body("pi1") && starts_paragraph("pi1"));
+        assert!(!is_body("s1") && !is_body("mt1") && !is_body("ip"));
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::Result;
+use roxmltree::{Document, Node, ParsingOptions};
+
+use super::{leading_number, Builder, Style};
+use crate::translations::TranslationMeta;
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|c| c.has_tag_name(name))
+        .and_then(|c| c.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// `<STYLE fs="italic">` or a red `<STYLE css="color:#ff0000">`
+fn style_of(node: Node, style: Style) -> Style {
+    let css = node.attribute("css").unwrap_or_default().to_lowercase();
+    let red = css.contains("#ff0000") || css.contains("red");
+    let italic = node.attribute("fs") == Some("italic") || css.contains("italic");
+    Style {
+        words_of_christ: style.words_of_christ || red,
+        italic: style.italic || italic,
+    }
+}
+
+/// ISO 639-3 (and 639-2/B) codes Zefania files use → the 2-letter codes
+/// the rest of the app uses
+const LANGUAGES: &[(&str, &str)] = &[
+    ("afr", "af"),
+    ("ara", "ar"),
+    ("ces", "cs"),
+    ("cze", "cs"),
+    ("chi", "zh"),
+    ("zho", "zh"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("ger", "de"),
+    ("dut", "nl"),
+    ("nld", "nl"),
+    ("ell", "el"),
+    ("gre", "el"),
+    ("eng", "en"),
+    ("fin", "fi"),
+    ("fra", "fr"),
+    ("fre", "fr"),
+    ("heb", "he"),
+    ("hun", "hu"),
+    ("ind", "id"),
+    ("ita", "it"),
+    ("jpn", "ja"),
+    ("kor", "ko"),
+    ("lat", "la"),
+    ("nor", "no"),
+    ("pol", "pl"),
+    ("por", "pt"),
+    ("ron", "ro"),
+    ("rum", "ro"),
+    ("rus", "ru"),
+    ("spa", "es"),
+    ("swa", "sw"),
+    ("swe", "sv"),
+    ("tur", "tr"),
+    ("ukr", "uk"),
+    ("vie", "vi"),
+];
+
+/// "eng" → "en"; codes without a 2-letter form are kept as they are
+fn language_code(language: &str) -> String {
+    let language = language.to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(long, _)| *long == language)
+        .map_or(language, |(_, short)| short.to_string())
+}
+
+struct Walker<'b> {
+    builder: &'b mut Builder,
+    book: Option<usize>,
+    chapter: u32,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, node: Node, style: Style) {
+        for child in node.children() {
+            if child.is_text() {
+                self.builder.text(child.text().unwrap_or_default(), style);
+                continue;
+            }
+            if !child.is_element() {
+                continue;
+            }
+
+            match child.tag_name().name() {
+                "INFORMATION" | "NOTE" | "CAPTION" | "REMARK" | "XREF" | "PROLOG" | "MEDIA" => {}
+                "BIBLEBOOK" => {
+                    // bnumber 1-66 follows the canon
+                    self.book = child
+                        .attribute("bnumber")
+                        .and_then(leading_number)
+                        .and_then(|n| (n as usize).checked_sub(1))
+                        .filter(|&b| b < 66);
+                    self.walk(child, style);
+                }
+                "CHAPTER" => {
+                    if let Some(n) = child.attribute("cnumber").and_then(leading_number) {
+                        self.chapter = n;
+                    }
+                    self.walk(child, style);
+                    self.builder.end_verse();
+                }
+                "VERS" => {
+                    let verse = child.attribute("vnumber").and_then(leading_number);
+                    if let (Some(book), Some(verse)) = (self.book, verse) {
+                        self.builder.start_verse(book, self.chapter, verse);
+                        self.walk(child, style);
+                    }
+                    self.builder.end_verse();
+                }
+                "BR" if child.attribute("art") == Some("x-p") => self.builder.paragraph(),
+                "STYLE" => self.walk(child, style_of(child, style)),
+                _ => self.walk(child, style),
+            }
+        }
+    }
+}
+
+pub(super) fn read(src: &str, builder: &mut Builder, meta: &mut TranslationMeta) -> Result<()> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let doc = Document::parse_with_options(src, options)?;
+    let root = doc.root_element();
+
+    if let Some(info) = root.children().find(|n| n.has_tag_name("INFORMATION")) {
+        if let Some(identifier) = child_text(info, "identifier") {
+            meta.abbreviation = identifier;
+        }
+        meta.name = child_text(info, "title").unwrap_or_default();
+        meta.language = language_code(&child_text(info, "language").unwrap_or_default());
+        meta.copyright = child_text(info, "rights");
+    }
+    if meta.name.is_empty() {
+        meta.name = root.attribute("biblename").unwrap_or_default().to_string();
+    }
+
+    let mut walker = Walker {
+        builder,
+        book: None,
+        chapter: 0,
+    };
+    walker.walk(root, Style::default());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{import, tests::assert_fixture};
+    use super::language_code;
+    use std::path::Path;
+
+    #[test]
+    fn test_zefania_import() {
+        let imported = import(Path::new("testdata/import/kjv.zefania.xml"), None).unwrap();
+        assert_eq!(imported.meta.abbreviation, "KJV");
+        assert_eq!(imported.meta.language, "en");
+        assert_fixture(&imported);
+    }
+
+    #[test]
+    fn test_language_code() {
+        assert_eq!(language_code("GER"), "de");
+        assert_eq!(language_code("de"), "de");
+        assert_eq!(language_code("haw"), "haw");
+    }
+}
@@ -0,0 +1,25 @@
+pub mod bible;
+#[cfg(feature = "audio")]
+pub mod capture;
+pub mod detect;
+pub mod eval;
+pub mod hymn;
+#[cfg(feature = "import")]
+pub mod import;
+#[cfg(feature = "propresenter")]
+pub mod output;
+pub mod paginate;
+pub mod parallel;
+#[cfg(feature = "propresenter")]
+pub mod propresenter;
+pub mod search;
+#[cfg(feature = "propresenter")]
+pub mod sink;
+pub mod template;
+pub mod translations;
+pub mod versification;
+
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "wasm")]
+mod wasm;
@@ -1,15 +1,16 @@
-// mod capture;
-mod detect;
-mod display;
-
 use anyhow::Result;
 use dotenv::dotenv;
 use std::env;
 use std::process::Command;
 
-// use crate::capture::speech_to_text;
-// use crate::detect::bible_verse;
-// use crate::display::stage_display;
+// Needs `--features vosk` (and libvosk to link)
+// use easy_sermon::capture::speech_to_text;
+// use easy_sermon::detect::detect;
+// use easy_sermon::output::{Routing, HOLD_LAYERS};
+// use easy_sermon::paginate::PageLimits;
+// use easy_sermon::propresenter::{ProPresenter, DEFAULT_ADDR};
+// use easy_sermon::sink::{ProPresenterSink, Router};
+// use easy_sermon::translations::{TranslationRegistry, TRANSLATIONS_DIR};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -42,6 +43,12 @@ async fn main() -> Result<()> {
         panic!("API server failed to start");
     }
 
+    // let translations = TranslationRegistry::scan(TRANSLATIONS_DIR)?;
+    // let propresenter = ProPresenter::new(DEFAULT_ADDR);
+    // let mut router = Router::default();
+    // // Verses detected during a video or announcement wait in `held` until it ends
+    // let mut held = router.hold_while_live(&propresenter, HOLD_LAYERS).await?;
+    // router = router.with_sink(ProPresenterSink::new(propresenter, Routing::default()));
     // for &line in &[
     //     "for the hope we have in john three verse sixteen",
     //     "keeping in mind the consequences in romans six verse twenty three",
@@ -54,12 +61,18 @@ async fn main() -> Result<()> {
     //     "finding the blueprint for peace in philippians four verses six and seven",
     //     "while on the great commission in matthew twenty eight verse nineteen through twenty",
     // ] {
-    //     let reference = bible_verse(line);
-    //     println!("\n🔍 Audio: {line} \n✅ Got: {reference:?}");
+    //     let detections = detect(line);
+    //     println!("\n🔍 Audio: {line} \n✅ Got: {detections:?}");
 
-    //     // Send verse(s) to Stage Display
-    //     for verse in &reference {
-    //         let _ = stage_display(verse).await;
+    //     // Send verse(s) to every display sink, in the translation the speaker named
+    //     let limits = PageLimits::default();
+    //     for d in &detections {
+    //         let _ = router.detected(&translations, d, &limits).await;
+    //     }
+    //     while let Ok(released) = held.try_recv() {
+    //         for d in &released {
+    //             let _ = router.detected(&translations, d, &limits).await;
+    //         }
     //     }
     // }
 
@@ -0,0 +1,481 @@
+//! Stage Display and audience-screen output over the ProPresenter TCP API
+
+use crate::detect::Detection;
+use crate::hymn::Hymn;
+use crate::paginate::{paginate, PageLimits, Pager};
+use crate::parallel::parallel;
+use crate::propresenter::{LiveState, ProPresenter, Status};
+use crate::template::Template;
+use crate::translations::TranslationRegistry;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A ProPresenter Message shown on the audience screen, and the names of the
+/// text tokens its theme uses
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AudienceMessage {
+    /// The Message's uuid, name or index
+    pub message: String,
+    pub reference_token: String,
+    pub text_token: String,
+    pub template: Template,
+}
+
+impl Default for AudienceMessage {
+    fn default() -> Self {
+        Self {
+            message: "Scripture".into(),
+            reference_token: "Reference".into(),
+            text_token: "Text".into(),
+            template: Template::default(),
+        }
+    }
+}
+
+/// Which screens a verse goes to, each with its own template
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Routing {
+    /// `None` leaves the stage message alone
+    pub stage: Option<Template>,
+    /// `None` leaves the audience screen alone
+    pub audience: Option<AudienceMessage>,
+    /// Trigger a pre-built presentation named after the reference when the
+    /// playlists or libraries have one, instead of sending text
+    pub presentations: bool,
+    /// How long to search for that presentation before sending text instead
+    pub search_timeout_ms: u64,
+}
+
+impl Default for Routing {
+    fn default() -> Self {
+        Self {
+            stage: Some(Template::default()),
+            audience: None,
+            presentations: false,
+            search_timeout_ms: 1000,
+        }
+    }
+}
+
+/// A passage paginated once; each output renders the current page with its
+/// own template as it is shown
+#[derive(Debug, Clone, Default)]
+pub struct Presentation {
+    pub reference: String,
+    pub translation: String,
+    pub pager: Pager,
+}
+
+impl Presentation {
+    pub fn new(reference: &str, translation: &str, passage: &str, limits: &PageLimits) -> Self {
+        Self {
+            reference: reference.to_string(),
+            translation: translation.to_string(),
+            pager: Pager::new(paginate(reference, passage, limits)),
+        }
+    }
+
+    /// A hymn announcement: "Hymn 245: Amazing Grace" as the reference and
+    /// the first line as the text
+    pub fn hymn(hymn: &Hymn, limits: &PageLimits) -> Self {
+        Self::new(&hymn.heading(), "", &hymn.first_line, limits)
+    }
+
+    /// The current page in `template`'s layout
+    pub fn render(&self, template: &Template) -> Option<String> {
+        let page = self.pager.current()?;
+        Some(template.render_page(
+            &self.reference,
+            &self.translation,
+            &page.text,
+            self.pager.index() + 1,
+            self.pager.pages().len(),
+        ))
+    }
+}
+
+/// Layers that mean something else has the room's attention
+pub const HOLD_LAYERS: &[&str] = &["media", "video_input", "announcements"];
+
+/// Keeps detected verses back while a video or announcement is live, and
+/// lets them through in order once it ends
+#[derive(Debug, Clone, Default)]
+pub struct Hold {
+    pub live: LiveState,
+    layers: Vec<String>,
+    held: Vec<Detection>,
+}
+
+impl Hold {
+    pub fn new(layers: &[&str]) -> Self {
+        Self {
+            layers: layers.iter().map(|l| l.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_holding(&self) -> bool {
+        self.live.is_live(&self.layers)
+    }
+
+    /// Whether `detection` may be shown now; if not it is held
+    pub fn admit(&mut self, detection: &Detection) -> bool {
+        if self.is_holding() {
+            self.held.push(detection.clone());
+            return false;
+        }
+        true
+    }
+
+    /// Track a status update; returns the held verses once the hold ends
+    pub fn update(&mut self, status: &Status) -> Vec<Detection> {
+        self.live.update(status);
+        if self.is_holding() {
+            return Vec::new();
+        }
+        std::mem::take(&mut self.held)
+    }
+}
+
+/// Trigger the library or playlist presentation for `verse`, if one turns
+/// up within `routing.search_timeout_ms`
+pub(crate) async fn presentation_display(
+    client: &ProPresenter,
+    routing: &Routing,
+    verse: &str,
+) -> bool {
+    let timeout = Duration::from_millis(routing.search_timeout_ms);
+    let Ok(found) = tokio::time::timeout(timeout, client.find_presentation(verse)).await else {
+        eprintln!("❌ Searching ProPresenter's library took over {timeout:?}");
+        return false;
+    };
+    match found {
+        Ok(Some(found)) => match client.trigger_presentation(&found).await {
+            Ok(()) => {
+                println!("✅ {verse} is live as \"{}\"", found.name());
+                true
+            }
+            Err(e) => {
+                eprintln!("❌ Error triggering \"{}\": {e}", found.name());
+                false
+            }
+        },
+        Ok(None) => false,
+        Err(e) => {
+            eprintln!("❌ Error searching ProPresenter's library: {e}");
+            false
+        }
+    }
+}
+
+/// Show the first page of a verse in the requested translation (or the
+/// registry's default) on every routed screen; step through the rest with
+/// the returned `Presentation`. A triggered library presentation leaves the
+/// `Presentation` without pages, as ProPresenter steps its slides.
+pub async fn verse_display(
+    client: &ProPresenter,
+    registry: &TranslationRegistry,
+    verse: &str,
+    translation: Option<&str>,
+    routing: &Routing,
+    limits: &PageLimits,
+) -> Result<Presentation> {
+    let abbreviation = registry.resolve_name(translation);
+    if routing.presentations && presentation_display(client, routing, verse).await {
+        return Ok(Presentation {
+            reference: verse.to_string(),
+            translation: abbreviation,
+            pager: Pager::default(),
+        });
+    }
+    let full_verse = registry.get(&abbreviation)?.passage(verse);
+    let presentation = Presentation::new(verse, &abbreviation, &full_verse, limits);
+    page_display(client, routing, &presentation).await?;
+    Ok(presentation)
+}
+
+/// Send the current page to every routed screen
+pub async fn page_display(
+    client: &ProPresenter,
+    routing: &Routing,
+    presentation: &Presentation,
+) -> Result<()> {
+    let Some(page) = presentation.pager.current() else {
+        return Ok(());
+    };
+    if let Some(template) = &routing.stage {
+        let text = presentation.render(template).unwrap_or_default();
+        send_stage_message(client, &text, &page.label).await?;
+    }
+    if let Some(audience) = &routing.audience {
+        let text = presentation.render(&audience.template).unwrap_or_default();
+        send_audience_message(client, audience, &presentation.reference, &text).await?;
+        println!("✅ {} is on the audience screen", page.label);
+    }
+    Ok(())
+}
+
+/// Show the remaining pages one after another, `interval` apart
+pub async fn advance_pages(
+    client: &ProPresenter,
+    routing: &Routing,
+    presentation: &mut Presentation,
+    interval: Duration,
+) -> Result<()> {
+    while presentation.pager.next_page().is_some() {
+        tokio::time::sleep(interval).await;
+        page_display(client, routing, presentation).await?;
+    }
+    Ok(())
+}
+
+/// Show a verse in several translations at once, e.g. KJV and RVR
+pub async fn parallel_display(
+    client: &ProPresenter,
+    registry: &TranslationRegistry,
+    verse: &str,
+    translations: &[&str],
+    routing: &Routing,
+    limits: &PageLimits,
+) -> Result<Presentation> {
+    let passage = parallel(registry, verse, translations)?;
+    let abbreviations = passage.translations.join("/");
+    let presentation = Presentation::new(verse, &abbreviations, &passage.to_text(), limits);
+    page_display(client, routing, &presentation).await?;
+    Ok(presentation)
+}
+
+/// Take the verse off every routed screen
+pub async fn clear_display(client: &ProPresenter, routing: &Routing) -> Result<()> {
+    if routing.stage.is_some() {
+        client.clear_stage_message().await?;
+    }
+    if let Some(audience) = &routing.audience {
+        client.clear_message(&audience.message).await?;
+    }
+    Ok(())
+}
+
+/// Trigger the audience Message with the reference and text filled in
+async fn send_audience_message(
+    client: &ProPresenter,
+    audience: &AudienceMessage,
+    reference: &str,
+    text: &str,
+) -> Result<()> {
+    let tokens = [
+        (audience.reference_token.as_str(), reference),
+        (audience.text_token.as_str(), text),
+    ];
+    client
+        .trigger_message(&audience.message, &tokens)
+        .await
+        .inspect_err(|e| eprintln!("❌ Error sending to the audience screen: {e}"))
+}
+
+/// Send `body` as the stage message; `shown` is what gets logged
+async fn send_stage_message(client: &ProPresenter, body: &str, shown: &str) -> Result<()> {
+    match client.stage_message(body).await {
+        Ok(()) => {
+            println!("✅ {shown} is on Stage Display");
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("❌ Error sending to Stage Display: {e}");
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::propresenter::mock::MockProPresenter;
+    use crate::template::VerseNumbers;
+
+    #[test]
+    fn test_hymn_presentation() {
+        let hymn = Hymn {
+            number: 245,
+            title: "Amazing Grace".into(),
+            first_line: "Amazing grace! how sweet the sound".into(),
+            page: None,
+        };
+        let presentation = Presentation::hymn(&hymn, &PageLimits::default());
+        assert_eq!(presentation.reference, "Hymn 245: Amazing Grace");
+        assert_eq!(
+            presentation.render(&Template::new("{reference}\n{text}")),
+            Some("Hymn 245: Amazing Grace\nAmazing grace! how sweet the sound".into())
+        );
+    }
+
+    #[test]
+    fn test_hold_while_video_is_live() {
+        let layers = |media: bool| Status::Layers([("media".to_string(), media)].into());
+        let verse = |reference: &str, translation: Option<&str>| Detection {
+            reference: reference.into(),
+            translation: translation.map(Into::into),
+        };
+        let mut hold = Hold::new(HOLD_LAYERS);
+        assert!(hold.admit(&verse("John 3:16", None)));
+
+        assert!(hold.update(&layers(true)).is_empty());
+        assert!(!hold.admit(&verse("Romans 8:28", Some("ESV"))));
+        assert!(!hold.admit(&verse("Psalm 23:1", None)));
+        assert!(hold.update(&layers(true)).is_empty());
+
+        assert_eq!(
+            hold.update(&layers(false)),
+            [verse("Romans 8:28", Some("ESV")), verse("Psalm 23:1", None)]
+        );
+        assert!(hold.admit(&verse("John 3:17", None)));
+    }
+
+    #[tokio::test]
+    async fn test_sending_verse_to_stage_display() {
+        let mock = MockProPresenter::start().await;
+        let client = ProPresenter::new(&mock.addr());
+
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+        let routing = Routing {
+            stage: Some(Template::new("{reference} ({translation})\n{text}")),
+            ..Routing::default()
+        };
+        let limits = PageLimits::default();
+        let shown = verse_display(&client, &registry, "John 3:16", None, &routing, &limits).await;
+        let shown = shown.unwrap();
+        assert_eq!(shown.pager.pages().len(), 1);
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0]["url"], "v1/stage/message");
+        let body = requests[0]["body"].as_str().unwrap();
+        assert!(body.starts_with("John 3:16 (KJV)\nJohn 3:16 — For God"));
+    }
+
+    #[tokio::test]
+    async fn test_routing_stage_and_audience_templates() {
+        let mock = MockProPresenter::start().await;
+        let client = ProPresenter::new(&mock.addr());
+
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+        let routing = Routing {
+            stage: Some(Template::new("{reference} {counter}\n{text}")),
+            audience: Some(AudienceMessage {
+                template: Template::new("{text}").with_verse_numbers(VerseNumbers::None),
+                ..AudienceMessage::default()
+            }),
+            ..Routing::default()
+        };
+        let limits = PageLimits::default();
+        verse_display(&client, &registry, "John 3:16", None, &routing, &limits)
+            .await
+            .unwrap();
+        clear_display(&client, &routing).await.unwrap();
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 4);
+        assert!(requests[0]["body"]
+            .as_str()
+            .unwrap()
+            .starts_with("John 3:16\nJohn 3:16 — For God"));
+
+        assert_eq!(requests[1]["url"], "v1/message/Scripture/trigger");
+        let tokens = &requests[1]["body"];
+        assert_eq!(tokens[0]["name"], "Reference");
+        assert_eq!(tokens[0]["text"]["text"], "John 3:16");
+        assert_eq!(tokens[1]["name"], "Text");
+        assert!(tokens[1]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("For God so loved"));
+
+        assert_eq!(requests[2]["method"], "DELETE");
+        assert_eq!(requests[3]["url"], "v1/message/Scripture/clear");
+    }
+
+    #[tokio::test]
+    async fn test_library_presentation_or_fallback() {
+        let mock = MockProPresenter::start().await;
+        let id = |uuid: &str, name: &str, index: u32| serde_json::json!({ "uuid": uuid, "name": name, "index": index });
+        mock.respond(
+            "v1/playlists",
+            serde_json::json!([{ "id": id("s1", "Services", 0), "field_type": "group", "children": [
+                { "id": id("p1", "Sunday", 0), "field_type": "playlist", "children": [] }
+            ] }]),
+        );
+        mock.respond(
+            "v1/playlist/p1",
+            serde_json::json!({ "items": [
+                { "id": id("w", "Welcome", 0), "type": "presentation" },
+                { "id": id("j", "John 3:16 (KJV)", 1), "type": "presentation" }
+            ] }),
+        );
+        mock.respond(
+            "v1/libraries",
+            serde_json::json!([id("l1", "Scripture", 0)]),
+        );
+        mock.respond(
+            "v1/library/l1",
+            serde_json::json!({ "items": [id("r", "Romans 8_28", 0)] }),
+        );
+        let client = ProPresenter::new(&mock.addr());
+
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+        let routing = Routing {
+            presentations: true,
+            ..Routing::default()
+        };
+        let limits = PageLimits::default();
+        let shown = verse_display(&client, &registry, "John 3:16", None, &routing, &limits)
+            .await
+            .unwrap();
+        assert!(shown.pager.pages().is_empty());
+        let last = |mock: &MockProPresenter| mock.requests().last().unwrap()["url"].clone();
+        assert_eq!(last(&mock), "v1/playlist/p1/1/trigger");
+
+        // No "John 3:17" presentation: the stage gets the text
+        verse_display(&client, &registry, "John 3:17", None, &routing, &limits)
+            .await
+            .unwrap();
+        assert_eq!(last(&mock), "v1/stage/message");
+    }
+
+    #[tokio::test]
+    async fn test_slow_library_falls_back_to_text() {
+        let mock = MockProPresenter::start().await;
+        let id =
+            |uuid: &str, name: &str| serde_json::json!({ "uuid": uuid, "name": name, "index": 0 });
+        mock.respond("v1/playlists", serde_json::json!([]));
+        mock.respond("v1/libraries", serde_json::json!([id("l1", "Scripture")]));
+        mock.respond(
+            "v1/library/l1",
+            serde_json::json!({ "items": [id("j", "John 3:16")] }),
+        );
+        mock.delay("v1/library/l1", Duration::from_secs(2));
+        let client = ProPresenter::new(&mock.addr());
+
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+        let routing = Routing {
+            presentations: true,
+            search_timeout_ms: 100,
+            ..Routing::default()
+        };
+        let limits = PageLimits::default();
+        let started = std::time::Instant::now();
+        let shown = verse_display(&client, &registry, "John 3:16", None, &routing, &limits)
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(shown.pager.pages().len(), 1);
+        let urls = mock
+            .requests()
+            .iter()
+            .map(|r| r["url"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(urls.last().unwrap(), "v1/stage/message");
+    }
+}
@@ -0,0 +1,285 @@
+//! Split passages into slide-sized pages, breaking between verses where
+//! possible, then between sentences, then between words
+
+use serde::{Deserialize, Serialize};
+
+/// How much text fits on one stage screen
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PageLimits {
+    pub max_chars: usize,
+    pub max_lines: usize,
+    /// Characters per line before the screen wraps; 0 never wraps
+    pub line_width: usize,
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        Self {
+            max_chars: 300,
+            max_lines: 8,
+            line_width: 48,
+        }
+    }
+}
+
+impl PageLimits {
+    fn fits(&self, text: &str) -> bool {
+        let lines: usize = text
+            .lines()
+            .map(|line| wrapped_lines(line, self.line_width))
+            .sum();
+        text.chars().count() <= self.max_chars && lines <= self.max_lines
+    }
+}
+
+/// Lines `line` takes up once word-wrapped at `width`
+pub(crate) fn wrapped_lines(line: &str, width: usize) -> usize {
+    if width == 0 {
+        return 1;
+    }
+    let mut lines = 1;
+    let mut used = 0;
+    for word in line.split_whitespace() {
+        let len = word.chars().count();
+        if used > 0 && used + 1 + len > width {
+            lines += 1;
+            used = 0;
+        }
+        used += if used > 0 { 1 + len } else { len };
+        // A word longer than the line wraps on its own
+        while used > width {
+            lines += 1;
+            used -= width;
+        }
+    }
+    lines
+}
+
+/// Split after `.`, `!`, `?`, `;` or `:` (and any closing quote) before a space
+fn sentences(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut at_end = false;
+
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() && at_end {
+            out.push(text[start..i].trim());
+            start = i;
+        }
+        at_end = matches!(c, '.' | '!' | '?' | ';' | ':')
+            || (at_end && matches!(c, '"' | '\'' | '”' | '’' | ')'));
+    }
+    out.push(text[start..].trim());
+    out.retain(|s| !s.is_empty());
+    out
+}
+
+/// Pieces of `text` that each fit, breaking at sentences then words
+fn pieces(text: &str, limits: &PageLimits) -> Vec<String> {
+    if limits.fits(text) {
+        return vec![text.to_string()];
+    }
+
+    let mut out = Vec::new();
+    for sentence in sentences(text) {
+        if limits.fits(sentence) {
+            out.push(sentence.to_string());
+            continue;
+        }
+        let mut current = String::new();
+        for word in sentence.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{current} {word}")
+            };
+            if !current.is_empty() && !limits.fits(&candidate) {
+                out.push(std::mem::take(&mut current));
+                current = word.to_string();
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() {
+            out.push(current);
+        }
+    }
+    out
+}
+
+/// One screen of a passage, e.g. "John 3:16-17 (1/2)"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Page {
+    pub label: String,
+    pub text: String,
+}
+
+/// Pages of a passage (one verse per line, as from `BibleStore::passage`)
+pub fn paginate(reference: &str, text: &str, limits: &PageLimits) -> Vec<Page> {
+    let mut pages: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for verse in text.lines().filter(|l| !l.trim().is_empty()) {
+        for (i, piece) in pieces(verse.trim(), limits).into_iter().enumerate() {
+            // Verses start a new line; the rest of a split verse runs on
+            let separator = if i == 0 { "\n" } else { " " };
+            let candidate = if current.is_empty() {
+                piece.clone()
+            } else {
+                format!("{current}{separator}{piece}")
+            };
+            if !current.is_empty() && !limits.fits(&candidate) {
+                pages.push(std::mem::replace(&mut current, piece));
+            } else {
+                current = candidate;
+            }
+        }
+    }
+    if !current.is_empty() {
+        pages.push(current);
+    }
+
+    let total = pages.len();
+    pages
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| Page {
+            label: if total > 1 {
+                format!("{reference} ({}/{total})", i + 1)
+            } else {
+                reference.to_string()
+            },
+            text,
+        })
+        .collect()
+}
+
+/// The page on screen, stepped by an operator or a timer
+#[derive(Debug, Clone, Default)]
+pub struct Pager {
+    pages: Vec<Page>,
+    index: usize,
+}
+
+impl Pager {
+    pub fn new(pages: Vec<Page>) -> Self {
+        Self { pages, index: 0 }
+    }
+
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    /// Zero-based position of the current page
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn current(&self) -> Option<&Page> {
+        self.pages.get(self.index)
+    }
+
+    /// Move to the next page; `None` (staying put) after the last one
+    pub fn next_page(&mut self) -> Option<&Page> {
+        if self.index + 1 >= self.pages.len() {
+            return None;
+        }
+        self.index += 1;
+        self.current()
+    }
+
+    /// Move to the previous page; `None` (staying put) on the first one
+    pub fn previous_page(&mut self) -> Option<&Page> {
+        self.index = self.index.checked_sub(1)?;
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHILIPPIANS: &str = "\
+Philippians 4:4 — Rejoice in the Lord alway: and again I say, Rejoice.
+Philippians 4:5 — Let your moderation be known unto all men. The Lord is at hand.
+Philippians 4:6 — Be careful for nothing; but in every thing by prayer and supplication with thanksgiving let your requests be made known unto God.
+Philippians 4:7 — And the peace of God, which passeth all understanding, shall keep your hearts and minds through Christ Jesus.";
+
+    #[test]
+    fn test_paginate_at_verse_boundaries() {
+        let limits = PageLimits {
+            max_chars: 220,
+            ..PageLimits::default()
+        };
+        let pages = paginate("Philippians 4:4-7", PHILIPPIANS, &limits);
+
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages[0].label, "Philippians 4:4-7 (1/3)");
+        assert_eq!(pages[0].text.lines().count(), 2);
+        assert!(pages[1].text.starts_with("Philippians 4:6"));
+        assert!(pages.iter().all(|p| limits.fits(&p.text)));
+
+        let one = paginate(
+            "Philippians 4:4",
+            PHILIPPIANS.lines().next().unwrap(),
+            &limits,
+        );
+        assert_eq!(one[0].label, "Philippians 4:4");
+    }
+
+    #[test]
+    fn test_paginate_splits_long_verses_at_sentences() {
+        let limits = PageLimits {
+            max_chars: 80,
+            max_lines: 3,
+            line_width: 40,
+        };
+        let pages = paginate(
+            "Philippians 4:6",
+            PHILIPPIANS.lines().nth(2).unwrap(),
+            &limits,
+        );
+
+        assert_eq!(pages[0].text, "Philippians 4:6 — Be careful for nothing;");
+        assert!(pages.iter().all(|p| limits.fits(&p.text)));
+        let joined = pages.iter().map(|p| p.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(joined.join(" "), PHILIPPIANS.lines().nth(2).unwrap());
+
+        assert_eq!(
+            sentences("Rejoice. “Again!” I say"),
+            ["Rejoice.", "“Again!”", "I say"]
+        );
+        assert_eq!(wrapped_lines("aaa bbb ccc", 7), 2);
+        assert_eq!(wrapped_lines("aaa bbb ccc", 0), 1);
+    }
+
+    #[test]
+    fn test_zero_line_width_does_not_wrap() {
+        let limits = PageLimits {
+            line_width: 0,
+            ..PageLimits::default()
+        };
+        let pages = paginate("Philippians 4:4-7", PHILIPPIANS, &limits);
+        assert_eq!(pages.len(), 2);
+        assert!(pages.iter().all(|p| limits.fits(&p.text)));
+    }
+
+    #[test]
+    fn test_pager_steps_through_pages() {
+        let limits = PageLimits {
+            max_chars: 100,
+            ..PageLimits::default()
+        };
+        let mut pager = Pager::new(paginate("Philippians 4:4-7", PHILIPPIANS, &limits));
+        assert!(pager.pages().len() > 2);
+
+        assert_eq!(pager.previous_page(), None);
+        assert!(pager.next_page().unwrap().label.contains("(2/"));
+        while pager.next_page().is_some() {}
+        assert_eq!(pager.index(), pager.pages().len() - 1);
+        let last = pager.index();
+        assert!(pager.previous_page().is_some());
+        assert_eq!(pager.index(), last - 1);
+    }
+}
@@ -0,0 +1,135 @@
+//! The same passage from several translations, aligned verse by verse
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::bible::{Lookup, Reference};
+use crate::translations::TranslationRegistry;
+
+/// One translation's rendering of a verse
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParallelText {
+    /// The verse in this translation's numbering, e.g. "Malachi 3:19"
+    pub reference: String,
+    /// `None` when the translation does not have the verse
+    pub text: Option<String>,
+    /// The translation deliberately leaves the verse out (see `Lookup::Absent`)
+    pub absent: bool,
+}
+
+/// A verse and its text in each translation, in the passage's order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParallelVerse {
+    pub reference: String,
+    pub texts: Vec<ParallelText>,
+}
+
+/// Rows are verses, columns are `translations`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParallelPassage {
+    pub reference: String,
+    pub translations: Vec<String>,
+    pub verses: Vec<ParallelVerse>,
+}
+
+impl ParallelPassage {
+    /// Each verse followed by an "ABBR: text" line per translation
+    pub fn to_text(&self) -> String {
+        self.verses
+            .iter()
+            .map(|verse| {
+                let mut lines = vec![verse.reference.clone()];
+                for (abbreviation, text) in self.translations.iter().zip(&verse.texts) {
+                    if let Some(text) = &text.text {
+                        lines.push(format!("{abbreviation}: {text}"));
+                    }
+                }
+                lines.join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Look up "John 3:16-17" in each of `translations`, mapping the (KJV
+/// numbered) reference into each translation's versification
+pub fn parallel(
+    registry: &TranslationRegistry,
+    reference: &str,
+    translations: &[&str],
+) -> Result<ParallelPassage> {
+    let parsed =
+        Reference::parse(reference).ok_or_else(|| anyhow!("Verse not found: {reference}"))?;
+
+    let mut columns = Vec::with_capacity(translations.len());
+    for abbreviation in translations {
+        let meta = registry
+            .meta(abbreviation)
+            .ok_or_else(|| anyhow!("Translation not installed: {abbreviation}"))?;
+        let bible = registry.get(abbreviation)?;
+        columns.push((meta.abbreviation.clone(), bible));
+    }
+
+    let verses = parsed
+        .verses()
+        .map(|v| ParallelVerse {
+            reference: parsed.verse_label(v),
+            texts: columns
+                .iter()
+                .map(|(_, bible)| {
+                    let (chapter, verse) = bible.locate(&parsed.book, parsed.chapter, v);
+                    let lookup = bible.lookup(&parsed.book, parsed.chapter, v);
+                    let text = match lookup {
+                        Lookup::Found(text) => Some(text.to_string()),
+                        Lookup::Absent | Lookup::NotFound => None,
+                    };
+                    ParallelText {
+                        reference: format!("{} {chapter}:{verse}", parsed.book),
+                        text,
+                        absent: lookup == Lookup::Absent,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(ParallelPassage {
+        reference: parsed.to_string(),
+        translations: columns.into_iter().map(|(a, _)| a).collect(),
+        verses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_passage_aligns_versification() {
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+
+        let john = parallel(&registry, "John 3:16-17", &["kjv", "WEB"]).unwrap();
+        assert_eq!(john.translations, ["KJV", "WEB"]);
+        assert_eq!(john.verses.len(), 2);
+        assert_eq!(john.verses[1].reference, "John 3:17");
+        assert!(john.verses[1].texts[1]
+            .text
+            .as_deref()
+            .unwrap()
+            .starts_with("For God didn't send"));
+
+        // Luther numbers Malachi 4 as Malachi 3:19-24
+        let malachi = parallel(&registry, "Malachi 4:1", &["KJV", "LUT", "WEB"]).unwrap();
+        let texts = &malachi.verses[0].texts;
+        assert_eq!(texts[1].reference, "Malachi 3:19");
+        assert!(texts[1].text.as_deref().unwrap().starts_with("Denn siehe"));
+        assert_eq!(texts[2].text, None);
+        let text = malachi.to_text();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "Malachi 4:1");
+        assert!(lines[1].starts_with("KJV: For, behold, the day cometh"));
+
+        assert!(parallel(&registry, "John 3:16", &["NIV"]).is_err());
+    }
+}
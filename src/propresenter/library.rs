@@ -0,0 +1,119 @@
+//! Find pre-built scripture presentations in ProPresenter's playlists and
+//! libraries by reference
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{ids, Id, ProPresenter};
+use crate::bible::title_score;
+
+/// A presentation that can be triggered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Found {
+    /// A playlist item, triggered by its index
+    Playlist {
+        playlist: Id,
+        item: Id,
+    },
+    Library {
+        library: Id,
+        presentation: Id,
+    },
+}
+
+impl Found {
+    pub fn name(&self) -> &str {
+        match self {
+            Found::Playlist { item, .. } => &item.name,
+            Found::Library { presentation, .. } => &presentation.name,
+        }
+    }
+}
+
+/// Playlists within playlist folders
+fn flatten_playlists(items: &Value, out: &mut Vec<Id>) {
+    for item in items.as_array().into_iter().flatten() {
+        if item["field_type"] == "playlist" {
+            if let Ok(id) = serde_json::from_value(item["id"].clone()) {
+                out.push(id);
+            }
+        }
+        flatten_playlists(&item["children"], out);
+    }
+}
+
+impl ProPresenter {
+    pub async fn libraries(&self) -> Result<Vec<Id>> {
+        ids(self.get("v1/libraries").await?)
+    }
+
+    /// Presentations in a library
+    pub async fn library(&self, library: &str) -> Result<Vec<Id>> {
+        let data = self.get(&format!("v1/library/{library}")).await?;
+        ids(data["items"].clone())
+    }
+
+    /// Every playlist, including those inside folders
+    pub async fn playlists(&self) -> Result<Vec<Id>> {
+        let mut playlists = Vec::new();
+        flatten_playlists(&self.get("v1/playlists").await?, &mut playlists);
+        Ok(playlists)
+    }
+
+    /// Items of a playlist, in order
+    pub async fn playlist(&self, playlist: &str) -> Result<Vec<Id>> {
+        let data = self.get(&format!("v1/playlist/{playlist}")).await?;
+        ids(data["items"].clone())
+    }
+
+    /// The best-named presentation for `reference`, searching playlists (what
+    /// is planned for today) before libraries
+    pub async fn find_presentation(&self, reference: &str) -> Result<Option<Found>> {
+        let mut best: Option<(u8, Found)> = None;
+        let mut consider = |score: u8, found: Found| {
+            if score > best.as_ref().map_or(0, |(s, _)| *s) {
+                best = Some((score, found));
+            }
+        };
+
+        for playlist in self.playlists().await? {
+            for item in self.playlist(&playlist.uuid).await? {
+                let score = title_score(&item.name, reference);
+                consider(
+                    score,
+                    Found::Playlist {
+                        playlist: playlist.clone(),
+                        item,
+                    },
+                );
+            }
+        }
+        for library in self.libraries().await? {
+            for presentation in self.library(&library.uuid).await? {
+                let score = title_score(&presentation.name, reference);
+                consider(
+                    score,
+                    Found::Library {
+                        library: library.clone(),
+                        presentation,
+                    },
+                );
+            }
+        }
+        Ok(best.map(|(_, found)| found))
+    }
+
+    /// Show the first slide of a presentation found by `find_presentation`
+    pub async fn trigger_presentation(&self, found: &Found) -> Result<()> {
+        let url = match found {
+            Found::Playlist { playlist, item } => {
+                format!("v1/playlist/{}/{}/trigger", playlist.uuid, item.index)
+            }
+            Found::Library {
+                library,
+                presentation,
+            } => format!("v1/library/{}/{}/trigger", library.uuid, presentation.uuid),
+        };
+        self.get(&url).await.map(drop)
+    }
+}
@@ -0,0 +1,142 @@
+//! A stand-in ProPresenter for tests: records each request and replies with
+//! canned data for its url
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::{JoinHandle, JoinSet};
+
+#[derive(Default)]
+struct State {
+    requests: Vec<Value>,
+    /// url → the whole reply, minus "url"
+    replies: HashMap<String, Value>,
+    /// url → how long to wait before replying
+    delays: HashMap<String, Duration>,
+    /// Lines to write to each open connection
+    connections: Vec<mpsc::UnboundedSender<String>>,
+}
+
+pub(crate) struct MockProPresenter {
+    addr: String,
+    state: Arc<Mutex<State>>,
+    server: JoinHandle<()>,
+}
+
+impl MockProPresenter {
+    pub(crate) async fn start() -> Self {
+        Self::bind("127.0.0.1:0").await
+    }
+
+    pub(crate) async fn bind(addr: &str) -> Self {
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let shared = state.clone();
+        let server = tokio::spawn(async move {
+            // Dropped with the server, closing every connection
+            let mut connections = JoinSet::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.spawn(serve(socket, shared.clone()));
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            server,
+        }
+    }
+
+    pub(crate) fn addr(&self) -> String {
+        self.addr.clone()
+    }
+
+    /// Reply to `url` with `{"data": data}`
+    pub(crate) fn respond(&self, url: &str, data: Value) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .replies
+            .insert(url.to_string(), json!({ "data": data }));
+    }
+
+    pub(crate) fn respond_error(&self, url: &str, error: &str) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .replies
+            .insert(url.to_string(), json!({ "error": error }));
+    }
+
+    /// Reply to `url` only after `delay`, without holding up other replies
+    pub(crate) fn delay(&self, url: &str, delay: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.delays.insert(url.to_string(), delay);
+    }
+
+    /// Send a status update to every connected client
+    pub(crate) fn push(&self, url: &str, data: Value) {
+        let line = format!("{}\r\n", json!({ "url": url, "data": data }));
+        let mut state = self.state.lock().unwrap();
+        state
+            .connections
+            .retain(|connection| connection.send(line.clone()).is_ok());
+    }
+
+    /// Every request received so far, oldest first
+    pub(crate) fn requests(&self) -> Vec<Value> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockProPresenter {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+async fn serve(socket: TcpStream, state: Arc<Mutex<State>>) {
+    let (reader, mut writer) = socket.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let (outgoing, mut pushed) = mpsc::unbounded_channel();
+    state.lock().unwrap().connections.push(outgoing.clone());
+
+    loop {
+        let reply = tokio::select! {
+            line = lines.next_line() => {
+                let Ok(Some(line)) = line else { break };
+                let Ok(request) = serde_json::from_str::<Value>(&line) else {
+                    continue;
+                };
+                let url = request["url"].as_str().unwrap_or_default().to_string();
+                let mut state = state.lock().unwrap();
+                let subscription = request["chunked"] == json!(true);
+                state.requests.push(request);
+                // Subscriptions are answered by `push`
+                if subscription {
+                    continue;
+                }
+                let mut reply = state.replies.get(&url).cloned().unwrap_or(json!({}));
+                reply["url"] = json!(url);
+                let reply = format!("{reply}\r\n");
+                if let Some(&delay) = state.delays.get(&url) {
+                    let outgoing = outgoing.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        let _ = outgoing.send(reply);
+                    });
+                    continue;
+                }
+                reply
+            }
+            Some(line) = pushed.recv() => line,
+        };
+        if writer.write_all(reply.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
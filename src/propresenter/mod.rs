@@ -0,0 +1,561 @@
+//! ProPresenter 7 TCP/IP API client: one persistent connection, CRLF-delimited
+//! JSON requests and replies, reconnecting with backoff when it drops
+
+mod library;
+#[cfg(test)]
+pub(crate) mod mock;
+mod status;
+
+pub use library::Found;
+pub use status::{LiveState, Status, STATUS_ENDPOINTS};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+/// ProPresenter > Settings > Network > TCP/IP
+pub const DEFAULT_ADDR: &str = "localhost:54346";
+
+#[derive(Debug, Clone, Copy)]
+pub struct ClientConfig {
+    /// How long to wait for each reply
+    pub timeout: Duration,
+    /// First and longest wait between reconnection attempts
+    pub min_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            min_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+/// `{"uuid", "name", "index"}`, how ProPresenter identifies layouts, looks,
+/// messages, props and timers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Id {
+    pub uuid: String,
+    pub name: String,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerOperation {
+    Start,
+    Stop,
+    Reset,
+}
+
+impl TimerOperation {
+    fn as_str(self) -> &'static str {
+        match self {
+            TimerOperation::Start => "start",
+            TimerOperation::Stop => "stop",
+            TimerOperation::Reset => "reset",
+        }
+    }
+}
+
+/// The `id` of every item in a list reply
+fn ids(data: Value) -> Result<Vec<Id>> {
+    let items: Vec<Value> = serde_json::from_value(data).context("Expected a list")?;
+    items
+        .into_iter()
+        .map(|item| {
+            let id = item.get("id").cloned().unwrap_or(item);
+            Ok(serde_json::from_value(id)?)
+        })
+        .collect()
+}
+
+struct Request {
+    url: String,
+    /// Written as is, except that a subscription only asks for endpoints not
+    /// already streaming
+    line: String,
+    /// The endpoints of a status subscription, which is re-sent after every
+    /// reconnect
+    subscription: Option<Vec<String>>,
+    reply: oneshot::Sender<Result<Value>>,
+}
+
+/// One chunked request streaming every endpoint in `endpoints`
+fn subscription_line<'a>(endpoints: impl IntoIterator<Item = &'a String>) -> String {
+    let endpoints = endpoints.into_iter().collect::<Vec<_>>();
+    let request = json!({
+        "url": "v1/status/updates",
+        "method": "POST",
+        "chunked": true,
+        "body": endpoints,
+    });
+    format!("{request}\r\n")
+}
+
+struct Awaiting {
+    url: String,
+    reply: oneshot::Sender<Result<Value>>,
+}
+
+/// A reply's `data`, or its `error` as an `Err`
+fn reply_result(reply: &Value) -> Result<Value> {
+    if let Some(error) = reply.get("error") {
+        let error = error.as_str().map(str::to_string);
+        bail!(
+            "ProPresenter: {}",
+            error.unwrap_or_else(|| reply["error"].to_string())
+        );
+    }
+    Ok(reply.get("data").cloned().unwrap_or(Value::Null))
+}
+
+/// Replies arrive in request order; match on `url` so a request ProPresenter
+/// skipped (or a caller gave up on) does not shift every later reply. Lines
+/// that answer no request are status updates.
+fn dispatch(pending: &mut VecDeque<Awaiting>, statuses: &broadcast::Sender<Status>, line: &str) {
+    pending.retain(|p| !p.reply.is_closed());
+    let Ok(reply) = serde_json::from_str::<Value>(line) else {
+        eprintln!("❌ Unreadable ProPresenter reply: {line}");
+        return;
+    };
+    let url = reply.get("url").and_then(Value::as_str);
+    let position = match url {
+        Some(url) => pending.iter().position(|p| p.url == url),
+        None => (!pending.is_empty()).then_some(0),
+    };
+    match (position.and_then(|i| pending.remove(i)), url) {
+        (Some(p), _) => {
+            let _ = p.reply.send(reply_result(&reply));
+        }
+        (None, Some(url)) => {
+            let data = reply.get("data").cloned().unwrap_or(Value::Null);
+            // No one listening is fine
+            let _ = statuses.send(Status::from_update(url, data));
+        }
+        (None, None) => {}
+    }
+}
+
+/// Owns the socket: connects, writes requests, reads replies, and reconnects
+/// with exponential backoff. Requests made while disconnected fail at once.
+async fn run(
+    addr: String,
+    config: ClientConfig,
+    mut requests: mpsc::Receiver<Request>,
+    connected: Arc<AtomicBool>,
+    statuses: broadcast::Sender<Status>,
+) {
+    let mut backoff = config.min_backoff;
+    // Each endpoint once, however often it is subscribed to
+    let mut subscriptions: BTreeSet<String> = BTreeSet::new();
+
+    loop {
+        let stream = match TcpStream::connect(&addr).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                let error = format!("Not connected to ProPresenter at {addr}: {e}");
+                let wait = tokio::time::sleep(backoff);
+                tokio::pin!(wait);
+                loop {
+                    tokio::select! {
+                        _ = &mut wait => break,
+                        request = requests.recv() => match request {
+                            // Sent once ProPresenter is reachable
+                            Some(Request { subscription: Some(endpoints), reply, .. }) => {
+                                subscriptions.extend(endpoints);
+                                let _ = reply.send(Ok(Value::Null));
+                            }
+                            Some(r) => {
+                                let _ = r.reply.send(Err(anyhow!(error.clone())));
+                            }
+                            None => return,
+                        },
+                    }
+                }
+                backoff = (backoff * 2).min(config.max_backoff);
+                continue;
+            }
+        };
+        backoff = config.min_backoff;
+        connected.store(true, Ordering::SeqCst);
+
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut pending = VecDeque::new();
+
+        let mut resubscribed = Ok(());
+        if !subscriptions.is_empty() {
+            let line = subscription_line(&subscriptions);
+            resubscribed = writer.write_all(line.as_bytes()).await;
+        }
+
+        let reason = loop {
+            if resubscribed.is_err() {
+                break "write failed";
+            }
+            tokio::select! {
+                request = requests.recv() => {
+                    let Some(request) = request else {
+                        connected.store(false, Ordering::SeqCst);
+                        return;
+                    };
+                    let line = match &request.subscription {
+                        Some(endpoints) => {
+                            // ProPresenter would stream a repeated endpoint twice
+                            let new = endpoints
+                                .iter()
+                                .filter(|e| !subscriptions.contains(*e))
+                                .collect::<Vec<_>>();
+                            (!new.is_empty()).then(|| subscription_line(new))
+                        }
+                        None => Some(request.line),
+                    };
+                    if let Some(line) = line {
+                        if let Err(e) = writer.write_all(line.as_bytes()).await {
+                            let _ = request.reply.send(Err(e.into()));
+                            break "write failed";
+                        }
+                    }
+                    match request.subscription {
+                        Some(endpoints) => {
+                            subscriptions.extend(endpoints);
+                            let _ = request.reply.send(Ok(Value::Null));
+                        }
+                        None => pending.push_back(Awaiting { url: request.url, reply: request.reply }),
+                    }
+                }
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) if line.trim().is_empty() => {}
+                    Ok(Some(line)) => dispatch(&mut pending, &statuses, &line),
+                    Ok(None) => break "connection closed",
+                    Err(_) => break "read failed",
+                },
+            }
+        };
+
+        connected.store(false, Ordering::SeqCst);
+        eprintln!("❌ ProPresenter {reason}, reconnecting");
+        for p in pending {
+            let _ = p.reply.send(Err(anyhow!("ProPresenter {reason}")));
+        }
+    }
+}
+
+/// Handle to the connection task; cheap to clone and share
+#[derive(Debug, Clone)]
+pub struct ProPresenter {
+    requests: mpsc::Sender<Request>,
+    connected: Arc<AtomicBool>,
+    statuses: broadcast::Sender<Status>,
+    timeout: Duration,
+}
+
+impl ProPresenter {
+    /// Connect to e.g. "localhost:54346" in the background; must be called
+    /// inside a tokio runtime
+    pub fn new(addr: &str) -> Self {
+        Self::with_config(addr, ClientConfig::default())
+    }
+
+    pub fn with_config(addr: &str, config: ClientConfig) -> Self {
+        let (requests, receiver) = mpsc::channel(64);
+        let connected = Arc::new(AtomicBool::new(false));
+        let (statuses, _) = broadcast::channel(64);
+        tokio::spawn(run(
+            addr.to_string(),
+            config,
+            receiver,
+            connected.clone(),
+            statuses.clone(),
+        ));
+        Self {
+            requests,
+            connected,
+            statuses,
+            timeout: config.timeout,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Send `{"url", "method", "body"}` and wait for the reply's `data`
+    pub async fn request(&self, method: &str, url: &str, body: Option<Value>) -> Result<Value> {
+        let mut request = json!({ "url": url, "method": method, "chunked": false });
+        if let Some(body) = body {
+            request["body"] = body;
+        }
+        self.send(method, url, format!("{request}\r\n"), None).await
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        url: &str,
+        line: String,
+        subscription: Option<Vec<String>>,
+    ) -> Result<Value> {
+        let (reply, response) = oneshot::channel();
+        self.requests
+            .send(Request {
+                url: url.to_string(),
+                line,
+                subscription,
+                reply,
+            })
+            .await
+            .map_err(|_| anyhow!("ProPresenter client stopped"))?;
+
+        tokio::time::timeout(self.timeout, response)
+            .await
+            .map_err(|_| anyhow!("ProPresenter did not reply to {method} {url}"))?
+            .map_err(|_| anyhow!("ProPresenter client stopped"))?
+    }
+
+    /// Stream changes to `endpoints` (e.g. `STATUS_ENDPOINTS`) as `Status`
+    /// events; the subscription survives reconnects
+    pub async fn subscribe(&self, endpoints: &[&str]) -> Result<broadcast::Receiver<Status>> {
+        // Listen first so the initial values are not missed
+        let statuses = self.statuses.subscribe();
+        let endpoints = endpoints.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        let line = subscription_line(&endpoints);
+        self.send("POST", "v1/status/updates", line, Some(endpoints))
+            .await?;
+        Ok(statuses)
+    }
+
+    async fn get(&self, url: &str) -> Result<Value> {
+        self.request("GET", url, None).await
+    }
+
+    pub async fn stage_message(&self, text: &str) -> Result<()> {
+        self.request("PUT", "v1/stage/message", Some(json!(text)))
+            .await
+            .map(drop)
+    }
+
+    pub async fn clear_stage_message(&self) -> Result<()> {
+        self.request("DELETE", "v1/stage/message", None)
+            .await
+            .map(drop)
+    }
+
+    pub async fn stage_layouts(&self) -> Result<Vec<Id>> {
+        ids(self.get("v1/stage/layouts").await?)
+    }
+
+    /// Show `layout` (uuid, name or index) on stage screen `screen`
+    pub async fn set_stage_layout(&self, screen: &str, layout: &str) -> Result<()> {
+        self.get(&format!("v1/stage/screen/{screen}/layout/{layout}"))
+            .await
+            .map(drop)
+    }
+
+    pub async fn messages(&self) -> Result<Vec<Id>> {
+        ids(self.get("v1/messages").await?)
+    }
+
+    /// Show a message, filling its text tokens by name
+    pub async fn trigger_message(&self, message: &str, tokens: &[(&str, &str)]) -> Result<()> {
+        let tokens = tokens
+            .iter()
+            .map(|(name, text)| json!({ "name": name, "text": { "text": text } }))
+            .collect::<Vec<_>>();
+        let url = format!("v1/message/{message}/trigger");
+        self.request("POST", &url, Some(json!(tokens)))
+            .await
+            .map(drop)
+    }
+
+    pub async fn clear_message(&self, message: &str) -> Result<()> {
+        self.get(&format!("v1/message/{message}/clear"))
+            .await
+            .map(drop)
+    }
+
+    pub async fn props(&self) -> Result<Vec<Id>> {
+        ids(self.get("v1/props").await?)
+    }
+
+    pub async fn trigger_prop(&self, prop: &str) -> Result<()> {
+        self.get(&format!("v1/prop/{prop}/trigger")).await.map(drop)
+    }
+
+    pub async fn clear_prop(&self, prop: &str) -> Result<()> {
+        self.get(&format!("v1/prop/{prop}/clear")).await.map(drop)
+    }
+
+    pub async fn looks(&self) -> Result<Vec<Id>> {
+        ids(self.get("v1/looks").await?)
+    }
+
+    pub async fn current_look(&self) -> Result<Id> {
+        let data = self.get("v1/look/current").await?;
+        let id = data.get("id").cloned().unwrap_or(data);
+        Ok(serde_json::from_value(id)?)
+    }
+
+    pub async fn trigger_look(&self, look: &str) -> Result<()> {
+        self.get(&format!("v1/look/{look}/trigger")).await.map(drop)
+    }
+
+    pub async fn timers(&self) -> Result<Vec<Id>> {
+        ids(self.get("v1/timers").await?)
+    }
+
+    pub async fn timer(&self, timer: &str, operation: TimerOperation) -> Result<()> {
+        self.get(&format!("v1/timer/{timer}/{}", operation.as_str()))
+            .await
+            .map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockProPresenter;
+    use super::*;
+
+    fn fast() -> ClientConfig {
+        ClientConfig {
+            timeout: Duration::from_secs(2),
+            min_backoff: Duration::from_millis(20),
+            max_backoff: Duration::from_millis(100),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requests_and_replies() {
+        let mock = MockProPresenter::start().await;
+        mock.respond(
+            "v1/looks",
+            json!([{ "id": { "uuid": "a1", "name": "Default", "index": 0 } }]),
+        );
+        mock.respond(
+            "v1/look/current",
+            json!({ "id": { "uuid": "a1", "name": "Default" } }),
+        );
+        let client = ProPresenter::with_config(&mock.addr(), fast());
+
+        client.stage_message("John 3:16").await.unwrap();
+        client.clear_stage_message().await.unwrap();
+        client
+            .trigger_message("Verse", &[("Reference", "John 3:16")])
+            .await
+            .unwrap();
+        client
+            .timer("Countdown", TimerOperation::Start)
+            .await
+            .unwrap();
+        assert!(client.is_connected());
+
+        let looks = client.looks().await.unwrap();
+        assert_eq!(looks[0].name, "Default");
+        assert_eq!(client.current_look().await.unwrap().uuid, "a1");
+
+        let requests = mock.requests();
+        assert_eq!(requests[0]["method"], "PUT");
+        assert_eq!(requests[0]["body"], "John 3:16");
+        assert_eq!(requests[1]["method"], "DELETE");
+        assert_eq!(requests[2]["url"], "v1/message/Verse/trigger");
+        assert_eq!(requests[2]["body"][0]["text"]["text"], "John 3:16");
+        assert_eq!(requests[3]["url"], "v1/timer/Countdown/start");
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_reconnect() {
+        let mock = MockProPresenter::start().await;
+        mock.respond_error("v1/props", "Not found");
+        let addr = mock.addr();
+        let client = ProPresenter::with_config(&addr, fast());
+
+        let error = client.props().await.unwrap_err();
+        assert_eq!(error.to_string(), "ProPresenter: Not found");
+
+        // ProPresenter quits: requests fail instead of hanging
+        drop(mock);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(client.stage_message("John 3:16").await.is_err());
+        assert!(!client.is_connected());
+
+        // ... and the client finds it again once it is back
+        let mock = MockProPresenter::bind(&addr).await;
+        let mut sent = false;
+        for _ in 0..50 {
+            if client.stage_message("John 3:17").await.is_ok() {
+                sent = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(sent);
+        assert_eq!(mock.requests().last().unwrap()["body"], "John 3:17");
+    }
+
+    #[tokio::test]
+    async fn test_status_subscription() {
+        let mock = MockProPresenter::start().await;
+        let addr = mock.addr();
+        let client = ProPresenter::with_config(&addr, fast());
+
+        let mut statuses = client.subscribe(STATUS_ENDPOINTS).await.unwrap();
+        // Once this is answered the subscription has arrived too
+        client.stage_message("John 3:16").await.unwrap();
+        let requests = mock.requests();
+        assert_eq!(requests[0]["url"], "v1/status/updates");
+        assert_eq!(requests[0]["chunked"], true);
+        assert_eq!(requests[0]["body"][0], "status/slide");
+
+        mock.push("status/layers", json!({ "media": true }));
+        let status = tokio::time::timeout(Duration::from_secs(2), statuses.recv());
+        let mut live = LiveState::default();
+        live.update(&status.await.unwrap().unwrap());
+        assert!(live.is_live(&["media"]));
+
+        // Subscribing again only asks for what isn't streaming yet
+        let _more = client
+            .subscribe(&["status/layers", "timers/current"])
+            .await
+            .unwrap();
+        let _same = client.subscribe(&["status/slide"]).await.unwrap();
+        client.stage_message("John 3:16").await.unwrap();
+        let subscriptions = mock
+            .requests()
+            .into_iter()
+            .filter(|r| r["url"] == "v1/status/updates")
+            .map(|r| r["body"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            subscriptions,
+            [json!(STATUS_ENDPOINTS), json!(["timers/current"])]
+        );
+
+        // A restarted ProPresenter is subscribed to again, each endpoint once
+        drop(mock);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let mock = MockProPresenter::bind(&addr).await;
+        for _ in 0..50 {
+            if client.stage_message("John 3:17").await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let requests = mock.requests();
+        assert_eq!(requests[0]["url"], "v1/status/updates");
+        let endpoints = requests[0]["body"].as_array().unwrap();
+        assert_eq!(endpoints.len(), STATUS_ENDPOINTS.len() + 1);
+        assert_eq!(requests[1]["url"], "v1/stage/message");
+    }
+}
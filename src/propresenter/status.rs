@@ -0,0 +1,128 @@
+//! Chunked status updates (`v1/status/updates`) and the live state they
+//! describe
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::Id;
+
+/// Current slide, active presentation, stage message and which layers are on
+pub const STATUS_ENDPOINTS: &[&str] = &[
+    "status/slide",
+    "presentation/active",
+    "stage/message",
+    "status/layers",
+];
+
+/// One update from a status subscription
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    /// Text of the current and next slides
+    Slide {
+        current: String,
+        next: String,
+    },
+    /// The presentation now live, if any
+    Presentation(Option<Id>),
+    StageMessage(String),
+    /// Layer name ("media", "video_input", "announcements", ...) → showing
+    Layers(BTreeMap<String, bool>),
+    Other {
+        url: String,
+        data: Value,
+    },
+}
+
+fn slide_text(data: &Value, slide: &str) -> String {
+    data[slide]["text"].as_str().unwrap_or_default().to_string()
+}
+
+impl Status {
+    pub fn from_update(url: &str, data: Value) -> Self {
+        match url {
+            "status/slide" => Status::Slide {
+                current: slide_text(&data, "current"),
+                next: slide_text(&data, "next"),
+            },
+            "presentation/active" => {
+                let id = data["presentation"]["id"].clone();
+                Status::Presentation(serde_json::from_value(id).ok())
+            }
+            "stage/message" => Status::StageMessage(data.as_str().unwrap_or_default().to_string()),
+            "status/layers" => match serde_json::from_value(data.clone()) {
+                Ok(layers) => Status::Layers(layers),
+                Err(_) => Status::Other {
+                    url: url.to_string(),
+                    data,
+                },
+            },
+            _ => Status::Other {
+                url: url.to_string(),
+                data,
+            },
+        }
+    }
+}
+
+/// What ProPresenter is showing, kept up to date from `Status` events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LiveState {
+    pub slide: String,
+    pub presentation: Option<Id>,
+    pub stage_message: String,
+    pub layers: BTreeMap<String, bool>,
+}
+
+impl LiveState {
+    pub fn update(&mut self, status: &Status) {
+        match status {
+            Status::Slide { current, .. } => self.slide = current.clone(),
+            Status::Presentation(id) => self.presentation = id.clone(),
+            Status::StageMessage(text) => self.stage_message = text.clone(),
+            Status::Layers(layers) => self.layers = layers.clone(),
+            Status::Other { .. } => {}
+        }
+    }
+
+    /// Whether any of `layers` is showing
+    pub fn is_live(&self, layers: &[impl AsRef<str>]) -> bool {
+        layers
+            .iter()
+            .any(|layer| self.layers.get(layer.as_ref()).copied().unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_updates() {
+        let mut live = LiveState::default();
+        let updates = [
+            (
+                "status/slide",
+                json!({ "current": { "text": "Amazing grace" }, "next": null }),
+            ),
+            (
+                "presentation/active",
+                json!({ "presentation": { "id": { "uuid": "p1", "name": "Hymns", "index": 2 } } }),
+            ),
+            ("stage/message", json!("John 3:16")),
+            ("status/layers", json!({ "media": true, "slide": true })),
+        ];
+        for (url, data) in updates {
+            live.update(&Status::from_update(url, data));
+        }
+
+        assert_eq!(live.slide, "Amazing grace");
+        assert_eq!(live.presentation.as_ref().unwrap().name, "Hymns");
+        assert_eq!(live.stage_message, "John 3:16");
+        assert!(live.is_live(&["media", "announcements"]));
+        assert!(!live.is_live(&["announcements"]));
+
+        let none = Status::from_update("presentation/active", json!({ "presentation": null }));
+        assert_eq!(none, Status::Presentation(None));
+    }
+}
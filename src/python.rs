@@ -0,0 +1,370 @@
+//! Python bindings, so the FastAPI server in `api/` runs the same detector
+//! as the Rust binary. Build with `maturin develop` (see pyproject.toml).
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::bible::{self, BibleStore};
+use crate::detect::{self, Detection};
+use crate::hymn::{self, Hymnal};
+use crate::paginate::{Page, PageLimits, Pager};
+use crate::parallel::{parallel, ParallelPassage};
+use crate::template::{Fields, Template};
+use crate::translations::TranslationRegistry;
+use crate::versification::Versification;
+
+/// (reference, translation) pairs, e.g. ("John 3:16", "NIV")
+type PyDetection = (String, Option<String>);
+
+/// RuntimeError with the message chain only (no Rust backtrace)
+fn py_err(e: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(format!("{e:#}"))
+}
+
+fn pair(d: Detection) -> PyDetection {
+    (d.reference, d.translation)
+}
+
+/// The same shape as `ParallelPassage` serialized, plus its stage `text`
+fn parallel_dict<'py>(py: Python<'py>, passage: &ParallelPassage) -> PyResult<Bound<'py, PyDict>> {
+    let verses = PyList::empty(py);
+    for verse in &passage.verses {
+        let texts = PyList::empty(py);
+        for text in &verse.texts {
+            let t = PyDict::new(py);
+            t.set_item("reference", &text.reference)?;
+            t.set_item("text", &text.text)?;
+            t.set_item("absent", text.absent)?;
+            texts.append(t)?;
+        }
+        let v = PyDict::new(py);
+        v.set_item("reference", &verse.reference)?;
+        v.set_item("texts", texts)?;
+        verses.append(v)?;
+    }
+
+    let d = PyDict::new(py);
+    d.set_item("reference", &passage.reference)?;
+    d.set_item("translations", &passage.translations)?;
+    d.set_item("verses", verses)?;
+    d.set_item("text", passage.to_text())?;
+    Ok(d)
+}
+
+/// bible_verse("john three verse sixteen") -> ["John 3:16"]
+#[pyfunction(name = "bible_verse")]
+fn py_bible_verse(text: &str) -> Vec<String> {
+    detect::bible_verse(text)
+}
+
+/// detect("in the niv john three verse sixteen") -> [("John 3:16", "NIV")]
+#[pyfunction(name = "detect")]
+fn py_detect(text: &str) -> Vec<PyDetection> {
+    detect::detect(text).into_iter().map(pair).collect()
+}
+
+/// render("{reference}\n{text}", "John 3:16", text, "KJV", "superscript") as one message
+#[pyfunction]
+#[pyo3(signature = (template, reference, text, translation = "", verse_numbers = "reference"))]
+fn render(
+    template: &str,
+    reference: &str,
+    text: &str,
+    translation: &str,
+    verse_numbers: &str,
+) -> PyResult<String> {
+    let template =
+        Template::new(template).with_verse_numbers(verse_numbers.parse().map_err(py_err)?);
+    Ok(template.render(&Fields {
+        reference,
+        translation,
+        text: &template.number_verses(text),
+        page: 1,
+        pages: 1,
+    }))
+}
+
+/// title_score("John 3_16 (KJV)", "John 3:16") -> 1; 2 for an exact name, 0 for no match
+#[pyfunction]
+fn title_score(title: &str, reference: &str) -> u8 {
+    bible::title_score(title, reference)
+}
+
+/// A translation loaded once, then queried per reference
+#[pyclass(name = "Bible", frozen)]
+struct PyBible {
+    bible: BibleStore,
+}
+
+#[pymethods]
+impl PyBible {
+    /// Bible(path, versification="kjv"), e.g. "hebrew" for Malachi 3:19-24
+    #[new]
+    #[pyo3(signature = (path, versification = "kjv"))]
+    fn new(path: &str, versification: &str) -> PyResult<Self> {
+        let bible = BibleStore::load(path).map_err(py_err)?;
+        Ok(Self {
+            bible: bible.with_versification(Versification::from_name(versification)),
+        })
+    }
+
+    /// verses("John 3:16-17") -> "John 3:16 — ...\nJohn 3:17 — ..."
+    fn verses(&self, reference: &str) -> String {
+        self.bible.passage(reference)
+    }
+}
+
+/// A hymnal index, see `hymn::hymn_announcement`
+#[pyclass(name = "Hymnal", frozen)]
+struct PyHymnal {
+    hymnal: Hymnal,
+}
+
+#[pymethods]
+impl PyHymnal {
+    #[new]
+    fn new(path: &str) -> PyResult<Self> {
+        Ok(Self {
+            hymnal: hymn::load_hymnal(path).map_err(py_err)?,
+        })
+    }
+
+    /// announcements("turn to hymn two forty five")
+    ///     -> [("Hymn 245: Amazing Grace", "Amazing grace! how sweet the sound")]
+    fn announcements(&self, text: &str) -> Vec<(String, String)> {
+        hymn::hymn_announcement(text, &self.hymnal)
+            .into_iter()
+            .map(|h| (h.heading(), h.first_line.clone()))
+            .collect()
+    }
+}
+
+/// Installed translations, see `translations::TranslationRegistry`
+#[pyclass(name = "Translations", frozen)]
+struct PyTranslations {
+    registry: TranslationRegistry,
+}
+
+#[pymethods]
+impl PyTranslations {
+    #[new]
+    fn new(dir: &str) -> PyResult<Self> {
+        Ok(Self {
+            registry: TranslationRegistry::scan(dir).map_err(py_err)?,
+        })
+    }
+
+    /// [{"abbreviation": "KJV", "name": "King James Version", ...}]
+    fn list<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.registry
+            .list()
+            .into_iter()
+            .map(|meta| {
+                let d = PyDict::new(py);
+                d.set_item("abbreviation", &meta.abbreviation)?;
+                d.set_item("name", &meta.name)?;
+                d.set_item("language", &meta.language)?;
+                d.set_item("copyright", &meta.copyright)?;
+                d.set_item("versification", &meta.versification)?;
+                Ok(d)
+            })
+            .collect()
+    }
+
+    #[getter]
+    fn default(&self) -> String {
+        self.registry.default_translation()
+    }
+
+    fn set_default(&self, abbreviation: &str) -> PyResult<()> {
+        self.registry.set_default(abbreviation).map_err(py_err)
+    }
+
+    /// The translation `verses` would use, e.g. resolve("NIV") -> "AKJV" when NIV is missing
+    #[pyo3(signature = (translation = None))]
+    fn resolve(&self, translation: Option<&str>) -> String {
+        self.registry.resolve_name(translation)
+    }
+
+    /// verses("John 3:16", "NIV"), falling back to the default translation
+    #[pyo3(signature = (reference, translation = None))]
+    fn verses(&self, reference: &str, translation: Option<&str>) -> PyResult<String> {
+        let bible = self.registry.resolve(translation).map_err(py_err)?;
+        Ok(bible.passage(reference))
+    }
+
+    /// search("be still and know") -> [{"reference", "text", "score"}], best first
+    #[pyo3(signature = (query, translation = None, limit = 10))]
+    fn search<'py>(
+        &self,
+        py: Python<'py>,
+        query: &str,
+        translation: Option<&str>,
+        limit: usize,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let abbreviation = self.registry.resolve_name(translation);
+        let index = self.registry.search_index(&abbreviation).map_err(py_err)?;
+        index
+            .search(query, limit)
+            .into_iter()
+            .map(|hit| {
+                let d = PyDict::new(py);
+                d.set_item("reference", hit.reference)?;
+                d.set_item("text", hit.text)?;
+                d.set_item("score", hit.score)?;
+                Ok(d)
+            })
+            .collect()
+    }
+
+    /// concordance("refuge") -> every reference containing the word
+    #[pyo3(signature = (word, translation = None))]
+    fn concordance(&self, word: &str, translation: Option<&str>) -> PyResult<Vec<String>> {
+        let abbreviation = self.registry.resolve_name(translation);
+        let index = self.registry.search_index(&abbreviation).map_err(py_err)?;
+        Ok(index
+            .concordance(word)
+            .into_iter()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// parallel("John 3:16", ["KJV", "RVR"]), aligned verse by verse
+    fn parallel<'py>(
+        &self,
+        py: Python<'py>,
+        reference: &str,
+        translations: Vec<String>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let translations = translations.iter().map(String::as_str).collect::<Vec<_>>();
+        let passage = parallel(&self.registry, reference, &translations).map_err(py_err)?;
+        parallel_dict(py, &passage)
+    }
+}
+
+fn page_pair(page: &Page) -> (String, String) {
+    (page.label.clone(), page.text.clone())
+}
+
+/// A passage split into stage-sized pages, see `paginate::Pager`
+#[pyclass(name = "Pager")]
+struct PyPager {
+    pager: Pager,
+}
+
+#[pymethods]
+impl PyPager {
+    /// Pager("John 3:16-17", text, max_chars=300, max_lines=8, line_width=48,
+    /// template="{text}", verse_numbers="reference", translation="")
+    #[new]
+    #[pyo3(signature = (
+        reference,
+        text,
+        max_chars = 300,
+        max_lines = 8,
+        line_width = 48,
+        template = "{text}",
+        verse_numbers = "reference",
+        translation = "",
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        reference: &str,
+        text: &str,
+        max_chars: usize,
+        max_lines: usize,
+        line_width: usize,
+        template: &str,
+        verse_numbers: &str,
+        translation: &str,
+    ) -> PyResult<Self> {
+        let limits = PageLimits {
+            max_chars,
+            max_lines,
+            line_width,
+        };
+        let template =
+            Template::new(template).with_verse_numbers(verse_numbers.parse().map_err(py_err)?);
+        let pages = template.pages(reference, translation, text, &limits);
+        Ok(Self {
+            pager: Pager::new(pages),
+        })
+    }
+
+    /// [(label, text), ...]
+    #[getter]
+    fn pages(&self) -> Vec<(String, String)> {
+        self.pager.pages().iter().map(page_pair).collect()
+    }
+
+    #[getter]
+    fn index(&self) -> usize {
+        self.pager.index()
+    }
+
+    /// (label, text) of the page on screen
+    #[getter]
+    fn current(&self) -> Option<(String, String)> {
+        self.pager.current().map(page_pair)
+    }
+
+    /// Step forward; False (staying put) after the last page
+    #[pyo3(name = "next")]
+    fn next_page(&mut self) -> bool {
+        self.pager.next_page().is_some()
+    }
+
+    /// Step back; False (staying put) on the first page
+    #[pyo3(name = "previous")]
+    fn previous_page(&mut self) -> bool {
+        self.pager.previous_page().is_some()
+    }
+}
+
+/// Detection over a live transcript, see `detect::Session`
+#[pyclass(name = "Session")]
+#[derive(Default)]
+struct PySession {
+    session: detect::Session,
+}
+
+#[pymethods]
+impl PySession {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// New detections in the next transcript; `final=True` ends the
+    /// utterance, so its references can be reported again after it
+    #[pyo3(signature = (text, r#final = false))]
+    fn feed(&mut self, text: &str, r#final: bool) -> Vec<PyDetection> {
+        let detections = if r#final {
+            self.session.feed_final(text)
+        } else {
+            self.session.feed(text)
+        };
+        detections.into_iter().map(pair).collect()
+    }
+
+    /// Every reference reported so far, oldest first
+    #[getter]
+    fn history(&self) -> Vec<String> {
+        self.session.history().to_vec()
+    }
+}
+
+#[pymodule]
+fn easy_sermon(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(py_bible_verse, m)?)?;
+    m.add_function(wrap_pyfunction!(py_detect, m)?)?;
+    m.add_function(wrap_pyfunction!(render, m)?)?;
+    m.add_function(wrap_pyfunction!(title_score, m)?)?;
+    m.add_class::<PyBible>()?;
+    m.add_class::<PyHymnal>()?;
+    m.add_class::<PyPager>()?;
+    m.add_class::<PySession>()?;
+    m.add_class::<PyTranslations>()?;
+    Ok(())
+}
@@ -0,0 +1,320 @@
+//! Full-text search over a translation: words, `"quoted phrases"` and
+//! `prefix*` queries, ranked with BM25
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::bible::BibleStore;
+
+/// BM25 term-frequency saturation and length normalisation
+const K1: f32 = 1.2;
+const B: f32 = 0.75;
+/// Extra weight for verses where the query words appear side by side
+const PHRASE_BONUS: f32 = 1.5;
+
+/// Lowercased words, keeping inner apostrophes ("lord's")
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+        .map(|w| w.trim_matches(|c| c == '\'' || c == '’').to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Word(String),
+    Prefix(String),
+    Phrase(Vec<String>),
+}
+
+/// `be still "and know" know*` → words, phrases and prefixes
+fn parse_query(query: &str) -> Vec<Term> {
+    let mut terms = Vec::new();
+    for (i, part) in query.split('"').enumerate() {
+        if i % 2 == 1 {
+            match tokenize(part).as_slice() {
+                [] => {}
+                [word] => terms.push(Term::Word(word.clone())),
+                words => terms.push(Term::Phrase(words.to_vec())),
+            }
+            continue;
+        }
+        for word in part.split_whitespace() {
+            let prefix = word.ends_with('*');
+            for token in tokenize(word) {
+                terms.push(if prefix {
+                    Term::Prefix(token)
+                } else {
+                    Term::Word(token)
+                });
+            }
+        }
+    }
+    terms
+}
+
+/// Verses in both, with their scores added; `None` is the first term
+fn intersect(acc: Option<HashMap<usize, f32>>, next: HashMap<usize, f32>) -> HashMap<usize, f32> {
+    match acc {
+        None => next,
+        Some(acc) => acc
+            .into_iter()
+            .filter_map(|(v, s)| next.get(&v).map(|n| (v, s + n)))
+            .collect(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub reference: String,
+    pub text: String,
+    pub score: f32,
+}
+
+#[derive(Debug)]
+struct IndexedVerse {
+    reference: String,
+    text: String,
+    words: usize,
+}
+
+/// Inverted index over one translation's verses
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    verses: Vec<IndexedVerse>,
+    /// word → (verse, positions of the word in it)
+    terms: BTreeMap<String, Vec<(usize, Vec<u32>)>>,
+    average_words: f32,
+}
+
+impl SearchIndex {
+    /// Index every verse, under its KJV-numbered reference
+    pub fn build(bible: &BibleStore) -> Self {
+        let mut index = Self::default();
+        let versification = bible.versification();
+
+        for (book, chapter, verse, text) in bible.verses() {
+            let Some((chapter, verse)) = versification.to_kjv(book, chapter, verse) else {
+                continue;
+            };
+            let id = index.verses.len();
+            let words = tokenize(text);
+            for (position, word) in words.iter().enumerate() {
+                let postings = index.terms.entry(word.clone()).or_default();
+                match postings.last_mut() {
+                    Some((last, positions)) if *last == id => positions.push(position as u32),
+                    _ => postings.push((id, vec![position as u32])),
+                }
+            }
+            index.verses.push(IndexedVerse {
+                reference: format!("{book} {chapter}:{verse}"),
+                text: text.to_string(),
+                words: words.len(),
+            });
+        }
+
+        let total: usize = index.verses.iter().map(|v| v.words).sum();
+        index.average_words = total as f32 / index.verses.len().max(1) as f32;
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.verses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verses.is_empty()
+    }
+
+    fn bm25(&self, df: usize, tf: usize, verse: usize) -> f32 {
+        let n = self.verses.len() as f32;
+        let idf = ((n - df as f32 + 0.5) / (df as f32 + 0.5) + 1.0).ln();
+        let tf = tf as f32;
+        let length = self.verses[verse].words as f32 / self.average_words.max(1.0);
+        idf * tf * (K1 + 1.0) / (tf + K1 * (1.0 - B + B * length))
+    }
+
+    /// Score per verse for one word
+    fn word_scores(&self, word: &str) -> HashMap<usize, f32> {
+        let Some(postings) = self.terms.get(word) else {
+            return HashMap::new();
+        };
+        postings
+            .iter()
+            .map(|(verse, positions)| (*verse, self.bm25(postings.len(), positions.len(), *verse)))
+            .collect()
+    }
+
+    fn prefix_scores(&self, prefix: &str) -> HashMap<usize, f32> {
+        let mut scores = HashMap::new();
+        for (word, _) in self
+            .terms
+            .range(prefix.to_string()..)
+            .take_while(|(w, _)| w.starts_with(prefix))
+        {
+            for (verse, score) in self.word_scores(word) {
+                *scores.entry(verse).or_insert(0.0) += score;
+            }
+        }
+        scores
+    }
+
+    /// Whether `words` appear consecutively in `verse`
+    fn has_phrase(&self, words: &[String], verse: usize) -> bool {
+        let positions = words
+            .iter()
+            .map(|w| {
+                self.terms.get(w).and_then(|p| {
+                    p.binary_search_by_key(&verse, |(v, _)| *v)
+                        .ok()
+                        .map(|i| &p[i].1)
+                })
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(positions) = positions else {
+            return false;
+        };
+        positions[0].iter().any(|&start| {
+            positions
+                .iter()
+                .enumerate()
+                .skip(1)
+                .all(|(offset, p)| p.contains(&(start + offset as u32)))
+        })
+    }
+
+    fn phrase_scores(&self, words: &[String]) -> HashMap<usize, f32> {
+        let mut scores: Option<HashMap<usize, f32>> = None;
+        for word in words {
+            let next = self.word_scores(word);
+            scores = Some(intersect(scores, next));
+        }
+        scores
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(verse, _)| self.has_phrase(words, *verse))
+            .map(|(verse, score)| (verse, score * PHRASE_BONUS))
+            .collect()
+    }
+
+    /// Verses matching every term of `query`, best first
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let terms = parse_query(query);
+        let mut scores: Option<HashMap<usize, f32>> = None;
+
+        for term in &terms {
+            let next = match term {
+                Term::Word(word) => self.word_scores(word),
+                Term::Prefix(prefix) => self.prefix_scores(prefix),
+                Term::Phrase(words) => self.phrase_scores(words),
+            };
+            scores = Some(intersect(scores, next));
+        }
+
+        // Unquoted words that happen to appear together rank first
+        let words = terms
+            .iter()
+            .map(|t| match t {
+                Term::Word(w) => Some(w.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|w| w.len() > 1);
+
+        let mut hits = scores
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(verse, mut score)| {
+                if words.as_ref().is_some_and(|w| self.has_phrase(w, verse)) {
+                    score *= PHRASE_BONUS;
+                }
+                (verse, score)
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        hits.into_iter()
+            .take(limit)
+            .map(|(verse, score)| SearchHit {
+                reference: self.verses[verse].reference.clone(),
+                text: self.verses[verse].text.clone(),
+                score,
+            })
+            .collect()
+    }
+
+    /// Every verse containing `word`, in canonical order
+    pub fn concordance(&self, word: &str) -> Vec<&str> {
+        let word = word.to_lowercase();
+        self.terms
+            .get(&word)
+            .map(|postings| {
+                postings
+                    .iter()
+                    .map(|(verse, _)| self.verses[*verse].reference.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PSALMS: &str = r#"{"books": [{"name": "Psalm", "chapters": [
+        {"chapter": 46, "verses": [
+            {"verse": 10, "text": "Be still, and know that I am God: I will be exalted among the heathen."},
+            {"verse": 11, "text": "The LORD of hosts is with us; the God of Jacob is our refuge."}
+        ]},
+        {"chapter": 100, "verses": [
+            {"verse": 3, "text": "Know ye that the LORD he is God: it is he that hath made us."}
+        ]},
+        {"chapter": 4, "verses": [
+            {"verse": 4, "text": "Stand in awe, and sin not: commune with your own heart upon your bed, and be still."}
+        ]}
+    ]}]}"#;
+
+    #[test]
+    fn test_search_words_phrases_and_prefixes() {
+        let index = SearchIndex::build(&BibleStore::from_json(PSALMS).unwrap());
+        assert_eq!(index.len(), 4);
+
+        let hits = index.search("be still and know", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].reference, "Psalm 46:10");
+
+        let phrase = index.search("\"be still\"", 10);
+        let refs = phrase
+            .iter()
+            .map(|h| h.reference.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(refs.len(), 2);
+        assert!(refs.contains(&"Psalm 4:4") && refs.contains(&"Psalm 46:10"));
+        assert!(index.search("\"still be\"", 10).is_empty());
+
+        let prefix = index.search("refu*", 10);
+        assert_eq!(prefix[0].reference, "Psalm 46:11");
+
+        // Rarer words outrank common ones
+        let ranked = index.search("god", 10);
+        assert_eq!(ranked.len(), 3);
+        assert!(index.search("exalted", 10)[0].score > ranked[2].score);
+
+        assert_eq!(index.concordance("LORD"), ["Psalm 46:11", "Psalm 100:3"]);
+        assert!(index.search("nothing here", 10).is_empty());
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query("still \"and know\" ref*"),
+            [
+                Term::Word("still".into()),
+                Term::Phrase(vec!["and".into(), "know".into()]),
+                Term::Prefix("ref".into()),
+            ]
+        );
+        assert_eq!(tokenize("the LORD's house"), ["the", "lord's", "house"]);
+    }
+}
@@ -0,0 +1,318 @@
+//! CasparCG over AMCP: plays a lower-third template on a channel and layer
+//! and updates its data with each verse
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use super::DisplaySink;
+use crate::output::Presentation;
+use crate::template::{Template, VerseNumbers};
+
+/// How template data is sent: JSON for HTML templates, XML for Flash ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateData {
+    #[default]
+    Json,
+    Xml,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CasparConfig {
+    pub addr: String,
+    pub channel: u16,
+    pub layer: u16,
+    /// Template path under CasparCG's template folder, without extension
+    pub template: String,
+    pub data: TemplateData,
+    /// Field names the template reads
+    pub reference_field: String,
+    pub text_field: String,
+    pub text_template: Template,
+}
+
+impl Default for CasparConfig {
+    fn default() -> Self {
+        Self {
+            addr: "localhost:5250".into(),
+            channel: 1,
+            layer: 20,
+            template: "lower-third".into(),
+            data: TemplateData::Json,
+            reference_field: "f0".into(),
+            text_field: "f1".into(),
+            // The reference has its own field
+            text_template: Template::default().with_verse_numbers(VerseNumbers::None),
+        }
+    }
+}
+
+/// AMCP string parameter: quoted, with `\`, `"` and newlines escaped
+fn quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl CasparConfig {
+    /// The template data for one verse, quoted for AMCP
+    pub fn template_data(&self, reference: &str, text: &str) -> String {
+        let fields = [(&self.reference_field, reference), (&self.text_field, text)];
+        let data = match self.data {
+            TemplateData::Json => {
+                let map: serde_json::Map<_, _> = fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), json!(value)))
+                    .collect();
+                serde_json::Value::Object(map).to_string()
+            }
+            TemplateData::Xml => {
+                let components: String = fields
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            "<componentData id=\"{}\"><data id=\"text\" value=\"{}\"/></componentData>",
+                            xml_escape(name),
+                            xml_escape(value)
+                        )
+                    })
+                    .collect();
+                format!("<templateData>{components}</templateData>")
+            }
+        };
+        quote(&data)
+    }
+}
+
+/// The open AMCP connection, and whether the template is on its layer
+#[derive(Default)]
+struct State {
+    stream: Option<BufStream<TcpStream>>,
+    added: bool,
+}
+
+/// Shows verses as a CasparCG template: `CG ADD` for the first verse,
+/// `CG UPDATE` for the next ones and `CG STOP` to clear
+pub struct CasparSink {
+    config: CasparConfig,
+    state: Mutex<State>,
+}
+
+impl CasparSink {
+    pub fn new(config: CasparConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Sends one command and reads its reply; the connection is dropped on
+    /// any error so the next command reconnects
+    async fn command(&self, state: &mut State, command: &str) -> Result<String> {
+        if state.stream.is_none() {
+            let stream = TcpStream::connect(&self.config.addr)
+                .await
+                .with_context(|| format!("Not connected to CasparCG at {}", self.config.addr))?;
+            *state = State {
+                stream: Some(BufStream::new(stream)),
+                added: false,
+            };
+        }
+        let stream = state.stream.as_mut().expect("connected above");
+        let result = exchange(stream, command).await;
+        if result.is_err() {
+            *state = State::default();
+        }
+        result
+    }
+
+    fn cg(&self, action: &str) -> String {
+        let config = &self.config;
+        format!("CG {}-{} {action} 1", config.channel, config.layer)
+    }
+}
+
+/// Writes a command and reads the reply: `202` alone, `201` with one line
+/// of data, `200` with lines up to a blank one, 4xx/5xx an error
+async fn exchange(stream: &mut BufStream<TcpStream>, command: &str) -> Result<String> {
+    stream
+        .write_all(format!("{command}\r\n").as_bytes())
+        .await?;
+    stream.flush().await?;
+
+    let mut status = String::new();
+    if stream.read_line(&mut status).await? == 0 {
+        bail!("CasparCG closed the connection");
+    }
+    let status = status.trim_end();
+    let mut data = String::new();
+    match status.get(..3) {
+        Some("201") => {
+            stream.read_line(&mut data).await?;
+        }
+        Some("200") => loop {
+            let mut line = String::new();
+            if stream.read_line(&mut line).await? == 0 || line.trim_end().is_empty() {
+                break;
+            }
+            data.push_str(&line);
+        },
+        Some(code) if code.starts_with('2') => {}
+        _ => bail!("CasparCG: {status}"),
+    }
+    Ok(data.trim_end().to_string())
+}
+
+#[async_trait]
+impl DisplaySink for CasparSink {
+    fn name(&self) -> &str {
+        "CasparCG"
+    }
+
+    async fn show(&self, presentation: &Presentation) -> Result<()> {
+        let config = &self.config;
+        let text = presentation
+            .render(&config.text_template)
+            .ok_or_else(|| anyhow!("Nothing to show for {}", presentation.reference))?;
+        let data = config.template_data(&presentation.reference, &text);
+
+        let mut state = self.state.lock().await;
+        let command = if state.added {
+            format!("{} {data}", self.cg("UPDATE"))
+        } else {
+            let template = quote(&config.template);
+            format!("{} {template} 1 {data}", self.cg("ADD"))
+        };
+        self.command(&mut state, &command).await?;
+        state.added = true;
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        self.command(&mut state, &self.cg("STOP")).await?;
+        state.added = false;
+        Ok(())
+    }
+
+    async fn health(&self) -> Result<()> {
+        let mut state = self.state.lock().await;
+        self.command(&mut state, "VERSION").await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paginate::PageLimits;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::BufReader;
+    use tokio::net::TcpListener;
+
+    /// A stand-in CasparCG that answers each command with the next line of
+    /// `script` (`202 OK` once it runs out) and records what it was sent
+    async fn mock_caspar(script: &[&str], commands: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let script: Vec<String> = script.iter().map(|s| s.to_string()).collect();
+        tokio::spawn(async move {
+            let mut script = script.into_iter();
+            while let Ok((stream, _)) = listener.accept().await {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(command)) = lines.next_line().await {
+                    commands.lock().unwrap().push(command);
+                    let reply = script.next().unwrap_or_else(|| "202 OK\r\n".into());
+                    if writer.write_all(reply.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        addr.to_string()
+    }
+
+    #[test]
+    fn test_template_data() {
+        let mut config = CasparConfig::default();
+        let data = config.template_data("John 3:16", "For \"God\" so loved\nthe world");
+        assert_eq!(
+            data,
+            r#""{\"f0\":\"John 3:16\",\"f1\":\"For \\\"God\\\" so loved\\nthe world\"}""#
+        );
+
+        config.data = TemplateData::Xml;
+        assert_eq!(
+            config.template_data("Ps 1:1", "a < b"),
+            r#""<templateData><componentData id=\"f0\"><data id=\"text\" value=\"Ps 1:1\"/></componentData><componentData id=\"f1\"><data id=\"text\" value=\"a &lt; b\"/></componentData></templateData>""#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_update_stop() {
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let script = ["201 VERSION OK\r\n2.3.3 Stable\r\n", "202 CG OK\r\n"];
+        let sink = CasparSink::new(CasparConfig {
+            addr: mock_caspar(&script, commands.clone()).await,
+            channel: 2,
+            layer: 10,
+            ..CasparConfig::default()
+        });
+
+        let presentation = |reference: &str| {
+            let passage = format!("{reference} — ...");
+            Presentation::new(reference, "KJV", &passage, &PageLimits::default())
+        };
+        sink.health().await.unwrap();
+        sink.show(&presentation("John 3:16")).await.unwrap();
+        sink.show(&presentation("John 3:17")).await.unwrap();
+        sink.clear().await.unwrap();
+        sink.show(&presentation("John 3:18")).await.unwrap();
+
+        assert_eq!(
+            *commands.lock().unwrap(),
+            [
+                "VERSION",
+                r#"CG 2-10 ADD 1 "lower-third" 1 "{\"f0\":\"John 3:16\",\"f1\":\"...\"}""#,
+                r#"CG 2-10 UPDATE 1 "{\"f0\":\"John 3:17\",\"f1\":\"...\"}""#,
+                "CG 2-10 STOP 1",
+                r#"CG 2-10 ADD 1 "lower-third" 1 "{\"f0\":\"John 3:18\",\"f1\":\"...\"}""#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_reply() {
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let script = ["404 CG ERROR\r\n"];
+        let sink = CasparSink::new(CasparConfig {
+            addr: mock_caspar(&script, commands.clone()).await,
+            ..CasparConfig::default()
+        });
+
+        let presentation = Presentation::new("Jude 1:25", "KJV", "...", &PageLimits::default());
+        let error = sink.show(&presentation).await.unwrap_err();
+        assert_eq!(error.to_string(), "CasparCG: 404 CG ERROR");
+        // Still not added, so the retry adds the template again
+        sink.show(&presentation).await.unwrap();
+        let commands = commands.lock().unwrap();
+        assert!(commands.iter().all(|c| c.starts_with("CG 1-20 ADD 1")));
+        assert_eq!(commands.len(), 2);
+    }
+}
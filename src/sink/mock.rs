@@ -0,0 +1,114 @@
+//! A stand-in HTTP server for the sinks that speak HTTP (vMix, OpenLP)
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::TcpListener;
+
+use crate::output::Presentation;
+use crate::paginate::PageLimits;
+
+/// What a stand-in was sent, oldest first
+pub(crate) type Recorder<T> = Arc<Mutex<Vec<T>>>;
+
+/// `reference` in the KJV, with `text` as its one verse
+pub(crate) fn presentation(reference: &str, text: &str) -> Presentation {
+    let passage = format!("{reference} — {text}");
+    Presentation::new(reference, "KJV", &passage, &PageLimits::default())
+}
+
+/// The vMix tests only look at the method, path and query
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "openlp"), allow(dead_code))]
+pub(crate) struct HttpRequest {
+    pub method: String,
+    /// Without the query
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Lowercase names
+    pub headers: HashMap<String, String>,
+    /// The JSON body, `Null` without one
+    pub body: Value,
+}
+
+impl HttpRequest {
+    /// A query parameter, "" when missing
+    pub fn param(&self, name: &str) -> &str {
+        self.query.get(name).map_or("", String::as_str)
+    }
+}
+
+/// An HTTP/1.1 server answering each request with `reply`'s status and JSON
+/// (`Null` for an empty body); returns its base url and a record of every
+/// request
+pub(crate) async fn mock_http<F>(reply: F) -> (String, Recorder<HttpRequest>)
+where
+    F: Fn(&HttpRequest) -> (u16, Value) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Recorder::default();
+    let (reply, recorded) = (Arc::new(reply), requests.clone());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let (reply, requests) = (reply.clone(), recorded.clone());
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut reader = BufReader::new(reader);
+                while let Some(request) = read_request(&mut reader).await {
+                    let (status, body) = reply(&request);
+                    requests.lock().unwrap().push(request);
+                    let body = if body.is_null() {
+                        String::new()
+                    } else {
+                        body.to_string()
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    );
+                    if writer.write_all(response.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    (format!("http://{addr}"), requests)
+}
+
+async fn read_request(reader: &mut BufReader<OwnedReadHalf>) -> Option<HttpRequest> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split(' ');
+    let method = parts.next()?.to_string();
+    let url = reqwest::Url::parse(&format!("http://mock{}", parts.next()?)).ok()?;
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).await.ok()?;
+        let Some((name, value)) = header.trim_end().split_once(": ") else {
+            break;
+        };
+        headers.insert(name.to_ascii_lowercase(), value.to_string());
+    }
+    let length = headers
+        .get("content-length")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await.ok()?;
+
+    Some(HttpRequest {
+        method,
+        path: url.path().to_string(),
+        query: url.query_pairs().into_owned().collect(),
+        headers,
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    })
+}
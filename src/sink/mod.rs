@@ -0,0 +1,402 @@
+//! Display sinks: every place a verse can be shown, behind one trait, and a
+//! router that fans each verse out to all of them
+
+#[cfg(feature = "casparcg")]
+mod casparcg;
+#[cfg(all(test, any(feature = "vmix", feature = "openlp")))]
+mod mock;
+#[cfg(feature = "obs")]
+mod obs;
+#[cfg(feature = "openlp")]
+mod openlp;
+#[cfg(feature = "osc")]
+mod osc;
+mod propresenter;
+#[cfg(feature = "vmix")]
+mod vmix;
+
+#[cfg(feature = "casparcg")]
+pub use casparcg::{CasparConfig, CasparSink, TemplateData};
+#[cfg(feature = "obs")]
+pub use obs::{ObsConfig, ObsSink};
+#[cfg(feature = "openlp")]
+pub use openlp::{OpenLpConfig, OpenLpSink};
+#[cfg(feature = "osc")]
+pub use osc::{OscConfig, OscSink};
+pub use propresenter::ProPresenterSink;
+#[cfg(feature = "vmix")]
+pub use vmix::{VmixConfig, VmixSink};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+use crate::detect::Detection;
+use crate::hymn::Hymn;
+use crate::output::{Hold, Presentation};
+use crate::paginate::PageLimits;
+use crate::propresenter::{ProPresenter, STATUS_ENDPOINTS};
+use crate::translations::TranslationRegistry;
+
+/// Somewhere verses are shown: ProPresenter, OBS, vMix, OpenLP, CasparCG, OSC receivers...
+#[async_trait]
+pub trait DisplaySink: Send + Sync {
+    /// Shown in logs and health reports
+    fn name(&self) -> &str;
+
+    /// Show the presentation's current page
+    async fn show(&self, presentation: &Presentation) -> Result<()>;
+
+    /// Take the verse off screen
+    async fn clear(&self) -> Result<()>;
+
+    /// `Err` with the reason when the sink can't show anything right now
+    async fn health(&self) -> Result<()>;
+}
+
+/// How one sink fared
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SinkReport {
+    pub sink: String,
+    pub error: Option<String>,
+}
+
+impl SinkReport {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Call {
+    Show,
+    Clear,
+    Health,
+}
+
+/// Fans each verse out to every sink at once. Each call has its own timeout
+/// and a failing, slow or panicking sink never holds up the others.
+#[derive(Clone)]
+pub struct Router {
+    sinks: Vec<Arc<dyn DisplaySink>>,
+    timeout: Duration,
+    /// Set by `hold_while_live`
+    hold: Option<Arc<Mutex<Hold>>>,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(3))
+    }
+}
+
+impl Router {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            sinks: Vec::new(),
+            timeout,
+            hold: None,
+        }
+    }
+
+    pub fn with_sink(mut self, sink: impl DisplaySink + 'static) -> Self {
+        self.add(Arc::new(sink));
+        self
+    }
+
+    pub fn add(&mut self, sink: Arc<dyn DisplaySink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn sinks(&self) -> impl Iterator<Item = &str> {
+        self.sinks.iter().map(|s| s.name())
+    }
+
+    /// Look up a verse and show its first page everywhere
+    pub async fn display(
+        &self,
+        registry: &TranslationRegistry,
+        verse: &str,
+        translation: Option<&str>,
+        limits: &PageLimits,
+    ) -> Result<(Presentation, Vec<SinkReport>)> {
+        let abbreviation = registry.resolve_name(translation);
+        let full_verse = registry.get(&abbreviation)?.passage(verse);
+        let presentation = Presentation::new(verse, &abbreviation, &full_verse, limits);
+        let reports = self.show(&presentation).await;
+        Ok((presentation, reports))
+    }
+
+    /// Follow ProPresenter's status and hold detected verses while one of
+    /// `layers` is live; the receiver gets the held verses as each hold ends
+    pub async fn hold_while_live(
+        &mut self,
+        client: &ProPresenter,
+        layers: &[&str],
+    ) -> Result<mpsc::UnboundedReceiver<Vec<Detection>>> {
+        let mut statuses = client.subscribe(STATUS_ENDPOINTS).await?;
+        let hold = Arc::new(Mutex::new(Hold::new(layers)));
+        self.hold = Some(hold.clone());
+        let (released, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let status = match statuses.recv().await {
+                    Ok(status) => status,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                let verses = hold.lock().unwrap().update(&status);
+                if !verses.is_empty() && released.send(verses).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+
+    /// Show a detected verse in the translation named with it, unless a
+    /// hold keeps it back for later (`None`)
+    pub async fn detected(
+        &self,
+        registry: &TranslationRegistry,
+        detection: &Detection,
+        limits: &PageLimits,
+    ) -> Result<Option<(Presentation, Vec<SinkReport>)>> {
+        if let Some(hold) = &self.hold {
+            if !hold.lock().unwrap().admit(detection) {
+                let reference = &detection.reference;
+                println!("⏸️ Holding {reference} until the video or announcement ends");
+                return Ok(None);
+            }
+        }
+        let translation = detection.translation.as_deref();
+        let shown = self
+            .display(registry, &detection.reference, translation, limits)
+            .await?;
+        Ok(Some(shown))
+    }
+
+    /// Show a hymn announcement everywhere
+    pub async fn hymn(&self, hymn: &Hymn, limits: &PageLimits) -> (Presentation, Vec<SinkReport>) {
+        let presentation = Presentation::hymn(hymn, limits);
+        let reports = self.show(&presentation).await;
+        (presentation, reports)
+    }
+
+    /// Show the current page on every sink; one report per sink, in order
+    pub async fn show(&self, presentation: &Presentation) -> Vec<SinkReport> {
+        self.fan_out(Call::Show, Some(presentation)).await
+    }
+
+    pub async fn clear(&self) -> Vec<SinkReport> {
+        self.fan_out(Call::Clear, None).await
+    }
+
+    pub async fn health(&self) -> Vec<SinkReport> {
+        self.fan_out(Call::Health, None).await
+    }
+
+    async fn fan_out(&self, call: Call, presentation: Option<&Presentation>) -> Vec<SinkReport> {
+        let mut tasks = JoinSet::new();
+        for (i, sink) in self.sinks.iter().enumerate() {
+            let (sink, timeout) = (sink.clone(), self.timeout);
+            let presentation = presentation.cloned().unwrap_or_default();
+            tasks.spawn(async move {
+                let result = tokio::time::timeout(timeout, async {
+                    match call {
+                        Call::Show => sink.show(&presentation).await,
+                        Call::Clear => sink.clear().await,
+                        Call::Health => sink.health().await,
+                    }
+                })
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("timed out after {timeout:?}")));
+                (i, result)
+            });
+        }
+
+        let mut results: Vec<Result<()>> = self
+            .sinks
+            .iter()
+            .map(|_| Err(anyhow!("sink panicked")))
+            .collect();
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok((i, result)) = joined {
+                results[i] = result;
+            }
+        }
+
+        self.sinks
+            .iter()
+            .zip(results)
+            .map(|(sink, result)| {
+                if let Err(e) = &result {
+                    eprintln!("❌ {} failed to {call:?}: {e}", sink.name());
+                }
+                SinkReport {
+                    sink: sink.name().to_string(),
+                    error: result.err().map(|e| e.to_string()),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hymn::{hymn_announcement, load_hymnal};
+    use crate::propresenter::mock::MockProPresenter;
+    use serde_json::json;
+
+    enum Behaviour {
+        Works,
+        Fails,
+        Hangs,
+        Panics,
+    }
+
+    struct TestSink {
+        name: &'static str,
+        behaviour: Behaviour,
+        shown: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl DisplaySink for TestSink {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn show(&self, presentation: &Presentation) -> Result<()> {
+            match self.behaviour {
+                Behaviour::Works => {
+                    let reference = presentation.reference.clone();
+                    self.shown.lock().unwrap().push(reference);
+                    Ok(())
+                }
+                Behaviour::Fails => Err(anyhow!("not connected")),
+                Behaviour::Hangs => {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok(())
+                }
+                Behaviour::Panics => panic!("sink bug"),
+            }
+        }
+
+        async fn clear(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn health(&self) -> Result<()> {
+            self.show(&Presentation::default()).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_one_dead_sink_does_not_block_the_others() {
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let sink = |name, behaviour| TestSink {
+            name,
+            behaviour,
+            shown: shown.clone(),
+        };
+        let router = Router::new(Duration::from_millis(100))
+            .with_sink(sink("hangs", Behaviour::Hangs))
+            .with_sink(sink("fails", Behaviour::Fails))
+            .with_sink(sink("panics", Behaviour::Panics))
+            .with_sink(sink("works", Behaviour::Works));
+        assert_eq!(
+            router.sinks().collect::<Vec<_>>(),
+            ["hangs", "fails", "panics", "works"]
+        );
+
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+        let limits = PageLimits::default();
+        let (presentation, reports) = router
+            .display(&registry, "John 3:16", None, &limits)
+            .await
+            .unwrap();
+        assert_eq!(presentation.translation, "KJV");
+        assert_eq!(*shown.lock().unwrap(), ["John 3:16"]);
+
+        let errors = reports
+            .iter()
+            .map(|r| r.error.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                Some("timed out after 100ms"),
+                Some("not connected"),
+                Some("sink panicked"),
+                None
+            ]
+        );
+        assert!(router.clear().await.iter().all(SinkReport::is_ok));
+    }
+
+    #[tokio::test]
+    async fn test_hymn_reaches_the_sinks() {
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let router = Router::default().with_sink(TestSink {
+            name: "works",
+            behaviour: Behaviour::Works,
+            shown: shown.clone(),
+        });
+        let hymnal = load_hymnal("hymnals/test_hymnal.json").unwrap();
+        let hymns = hymn_announcement("please turn to hymn number seventy", &hymnal);
+
+        let (_, reports) = router.hymn(hymns[0], &PageLimits::default()).await;
+        assert!(reports.iter().all(SinkReport::is_ok));
+        assert_eq!(*shown.lock().unwrap(), ["Hymn 70: Blessed Assurance"]);
+    }
+
+    #[tokio::test]
+    async fn test_hold_while_video_is_live() {
+        let mock = MockProPresenter::start().await;
+        let client = ProPresenter::new(&mock.addr());
+        let shown = Arc::new(Mutex::new(Vec::new()));
+        let mut router = Router::default().with_sink(TestSink {
+            name: "works",
+            behaviour: Behaviour::Works,
+            shown: shown.clone(),
+        });
+        let mut released = router.hold_while_live(&client, &["media"]).await.unwrap();
+        // Once this is answered the subscription has arrived too
+        client.stage_message("").await.unwrap();
+
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+        let limits = PageLimits::default();
+        let verse = |reference: &str| Detection {
+            reference: reference.into(),
+            translation: Some("KJV".into()),
+        };
+        let john = verse("John 3:16");
+        let detected = router.detected(&registry, &john, &limits).await;
+        assert!(detected.unwrap().is_some());
+
+        mock.push("status/layers", json!({ "media": true }));
+        // The hold starts once the update has been read
+        for _ in 0..50 {
+            let hold = router.hold.as_ref().unwrap();
+            if hold.lock().unwrap().is_holding() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let romans = verse("Romans 8:28");
+        let detected = router.detected(&registry, &romans, &limits).await;
+        assert!(detected.unwrap().is_none());
+        assert_eq!(*shown.lock().unwrap(), ["John 3:16"]);
+
+        mock.push("status/layers", json!({ "media": false }));
+        let verses = tokio::time::timeout(Duration::from_secs(2), released.recv());
+        assert_eq!(verses.await.unwrap().unwrap(), [romans]);
+    }
+}
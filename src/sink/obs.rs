@@ -0,0 +1,405 @@
+//! OBS Studio over obs-websocket v5: sets a text source to the verse and
+//! shows a scene item (e.g. a lower third), hiding it again after a while
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use super::DisplaySink;
+use crate::output::Presentation;
+use crate::template::{Template, VerseNumbers};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// obs-websocket opcodes
+const HELLO: u64 = 0;
+const IDENTIFY: u64 = 1;
+const IDENTIFIED: u64 = 2;
+const REQUEST: u64 = 6;
+const REQUEST_RESPONSE: u64 = 7;
+
+/// Tools > WebSocket Server Settings in OBS
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ObsConfig {
+    pub url: String,
+    pub password: Option<String>,
+    /// Text (GDI+ / FreeType 2) source that gets the verse
+    pub input: String,
+    /// Scene item shown with each verse, e.g. a lower-third group
+    pub scene: Option<String>,
+    pub scene_item: Option<String>,
+    /// Hide the scene item again this many seconds after the last verse; zero
+    /// or less keeps it up until cleared
+    pub auto_hide_secs: Option<f64>,
+    pub template: Template,
+}
+
+impl Default for ObsConfig {
+    fn default() -> Self {
+        Self {
+            url: "ws://localhost:4455".into(),
+            password: None,
+            input: "Scripture".into(),
+            scene: None,
+            scene_item: None,
+            auto_hide_secs: None,
+            // The reference is on its own line, not before the verse too
+            template: Template::new("{text}\n{reference}").with_verse_numbers(VerseNumbers::None),
+        }
+    }
+}
+
+/// The Identify `authentication` string:
+/// base64(sha256(base64(sha256(password + salt)) + challenge))
+pub fn authentication(password: &str, salt: &str, challenge: &str) -> String {
+    let secret = STANDARD.encode(Sha256::digest(format!("{password}{salt}")));
+    STANDARD.encode(Sha256::digest(format!("{secret}{challenge}")))
+}
+
+async fn receive(socket: &mut Socket) -> Result<Value> {
+    loop {
+        match socket.next().await.context("OBS closed the connection")?? {
+            Message::Text(text) => return Ok(serde_json::from_str(&text)?),
+            Message::Close(_) => bail!("OBS closed the connection"),
+            _ => {}
+        }
+    }
+}
+
+async fn send(socket: &mut Socket, op: u64, d: Value) -> Result<()> {
+    let message = json!({ "op": op, "d": d }).to_string();
+    socket.send(Message::Text(message.into())).await?;
+    Ok(())
+}
+
+struct Connection {
+    config: ObsConfig,
+    socket: Mutex<Option<Socket>>,
+    next_id: AtomicU64,
+}
+
+impl Connection {
+    /// Connect, answer Hello with Identify and wait to be Identified
+    async fn connect(&self) -> Result<Socket> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(self.config.url.as_str())
+            .await
+            .with_context(|| format!("Not connected to OBS at {}", self.config.url))?;
+
+        let hello = receive(&mut socket).await?;
+        if hello["op"] != HELLO {
+            bail!("Expected Hello from OBS, got {hello}");
+        }
+        let mut identify = json!({ "rpcVersion": 1, "eventSubscriptions": 0 });
+        if let Some(auth) = hello["d"].get("authentication") {
+            let password = self
+                .config
+                .password
+                .as_deref()
+                .context("OBS wants a password")?;
+            let salt = auth["salt"].as_str().unwrap_or_default();
+            let challenge = auth["challenge"].as_str().unwrap_or_default();
+            identify["authentication"] = json!(authentication(password, salt, challenge));
+        }
+        send(&mut socket, IDENTIFY, identify).await?;
+
+        let identified = receive(&mut socket).await?;
+        if identified["op"] != IDENTIFIED {
+            bail!("OBS refused to identify us (wrong password?)");
+        }
+        Ok(socket)
+    }
+
+    /// Send one request and wait for its response, reconnecting if needed
+    async fn request(&self, request_type: &str, data: Value) -> Result<Value> {
+        let mut socket = self.socket.lock().await;
+        if socket.is_none() {
+            *socket = Some(self.connect().await?);
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let result: Result<Value> = async {
+            let ws = socket.as_mut().unwrap();
+            let request = json!({
+                "requestType": request_type,
+                "requestId": id,
+                "requestData": data,
+            });
+            send(ws, REQUEST, request).await?;
+            loop {
+                let response = receive(ws).await?;
+                if response["op"] == REQUEST_RESPONSE && response["d"]["requestId"] == id.as_str() {
+                    return Ok(response["d"].clone());
+                }
+            }
+        }
+        .await;
+
+        // Start afresh next time rather than reuse a broken socket
+        let response = result.inspect_err(|_| *socket = None)?;
+        let status = &response["requestStatus"];
+        if status["result"] != true {
+            bail!(
+                "OBS {request_type}: {}",
+                status["comment"].as_str().unwrap_or("failed")
+            );
+        }
+        Ok(response["responseData"].clone())
+    }
+
+    async fn set_text(&self, text: &str) -> Result<()> {
+        let data = json!({
+            "inputName": self.config.input,
+            "inputSettings": { "text": text },
+            "overlay": true,
+        });
+        self.request("SetInputSettings", data).await.map(drop)
+    }
+
+    async fn set_visible(&self, visible: bool) -> Result<()> {
+        let (Some(scene), Some(item)) = (&self.config.scene, &self.config.scene_item) else {
+            return Ok(());
+        };
+        let found = self
+            .request(
+                "GetSceneItemId",
+                json!({ "sceneName": scene, "sourceName": item }),
+            )
+            .await?;
+        let data = json!({
+            "sceneName": scene,
+            "sceneItemId": found["sceneItemId"],
+            "sceneItemEnabled": visible,
+        });
+        self.request("SetSceneItemEnabled", data).await.map(drop)
+    }
+}
+
+/// Shows verses in OBS
+pub struct ObsSink {
+    connection: Arc<Connection>,
+    hide: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl ObsSink {
+    /// Connects on first use
+    pub fn new(config: ObsConfig) -> Self {
+        Self {
+            connection: Arc::new(Connection {
+                config,
+                socket: Mutex::new(None),
+                next_id: AtomicU64::new(1),
+            }),
+            hide: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Replace any pending auto-hide with `next`
+    fn replace_hide(&self, next: Option<JoinHandle<()>>) {
+        if let Some(previous) = std::mem::replace(&mut *self.hide.lock().unwrap(), next) {
+            previous.abort();
+        }
+    }
+}
+
+impl Drop for ObsSink {
+    fn drop(&mut self) {
+        self.replace_hide(None);
+    }
+}
+
+#[async_trait]
+impl DisplaySink for ObsSink {
+    fn name(&self) -> &str {
+        "OBS"
+    }
+
+    async fn show(&self, presentation: &Presentation) -> Result<()> {
+        let connection = &self.connection;
+        let text = presentation
+            .render(&connection.config.template)
+            .ok_or_else(|| anyhow!("Nothing to show for {}", presentation.reference))?;
+        self.replace_hide(None);
+        connection.set_text(&text).await?;
+        connection.set_visible(true).await?;
+
+        let auto_hide = connection.config.auto_hide_secs.filter(|&secs| secs > 0.0);
+        if let Some(after) = auto_hide.and_then(|secs| Duration::try_from_secs_f64(secs).ok()) {
+            let connection = connection.clone();
+            self.replace_hide(Some(tokio::spawn(async move {
+                tokio::time::sleep(after).await;
+                if let Err(e) = connection.set_visible(false).await {
+                    eprintln!("❌ Error hiding the OBS scene item: {e}");
+                }
+            })));
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.replace_hide(None);
+        self.connection.set_visible(false).await?;
+        self.connection.set_text("").await
+    }
+
+    async fn health(&self) -> Result<()> {
+        self.connection
+            .request("GetVersion", json!({}))
+            .await
+            .map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paginate::PageLimits;
+    use std::sync::Mutex as StdMutex;
+    use tokio::net::TcpListener;
+
+    const SALT: &str = "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=";
+    const CHALLENGE: &str = "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY=";
+
+    /// A stand-in obs-websocket server with password "secret" that records
+    /// each request
+    async fn mock_obs(requests: Arc<StdMutex<Vec<Value>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let requests = requests.clone();
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(MaybeTlsStream::Plain(stream))
+                        .await
+                        .unwrap();
+                    let auth = json!({ "challenge": CHALLENGE, "salt": SALT });
+                    let hello = json!({ "rpcVersion": 1, "authentication": auth });
+                    send(&mut ws, HELLO, hello).await.unwrap();
+
+                    let identify = receive(&mut ws).await.unwrap();
+                    if identify["d"]["authentication"] != authentication("secret", SALT, CHALLENGE)
+                    {
+                        return;
+                    }
+                    send(&mut ws, IDENTIFIED, json!({ "negotiatedRpcVersion": 1 }))
+                        .await
+                        .unwrap();
+
+                    while let Ok(request) = receive(&mut ws).await {
+                        let d = &request["d"];
+                        requests.lock().unwrap().push(d.clone());
+                        let response = json!({
+                            "requestType": d["requestType"],
+                            "requestId": d["requestId"],
+                            "requestStatus": { "result": true, "code": 100 },
+                            "responseData": { "sceneItemId": 7 },
+                        });
+                        send(&mut ws, REQUEST_RESPONSE, response).await.unwrap();
+                    }
+                });
+            }
+        });
+        format!("ws://{addr}")
+    }
+
+    #[test]
+    fn test_authentication() {
+        // From the obs-websocket v5 protocol docs' example steps
+        assert_eq!(
+            authentication("supersecretpassword", SALT, CHALLENGE),
+            "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4="
+        );
+    }
+
+    #[tokio::test]
+    async fn test_show_and_auto_hide() {
+        let requests = Arc::new(StdMutex::new(Vec::new()));
+        let url = mock_obs(requests.clone()).await;
+        let sink = ObsSink::new(ObsConfig {
+            url,
+            password: Some("secret".into()),
+            scene: Some("Service".into()),
+            scene_item: Some("Lower Third".into()),
+            auto_hide_secs: Some(0.05),
+            ..ObsConfig::default()
+        });
+
+        let passage = "John 3:16 — For God so loved the world.";
+        let presentation = Presentation::new("John 3:16", "KJV", passage, &PageLimits::default());
+        sink.show(&presentation).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let requests = requests.lock().unwrap().clone();
+        let types = requests
+            .iter()
+            .map(|r| r["requestType"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            [
+                "SetInputSettings",
+                "GetSceneItemId",
+                "SetSceneItemEnabled",
+                "GetSceneItemId",
+                "SetSceneItemEnabled"
+            ]
+        );
+        assert_eq!(requests[0]["requestData"]["inputName"], "Scripture");
+        assert_eq!(
+            requests[0]["requestData"]["inputSettings"]["text"],
+            "For God so loved the world.\nJohn 3:16"
+        );
+        assert_eq!(requests[2]["requestData"]["sceneItemId"], 7);
+        assert_eq!(requests[2]["requestData"]["sceneItemEnabled"], true);
+        assert_eq!(requests[4]["requestData"]["sceneItemEnabled"], false);
+    }
+
+    #[tokio::test]
+    async fn test_no_auto_hide_without_a_positive_delay() {
+        for secs in [0.0, -1.0] {
+            let requests = Arc::new(StdMutex::new(Vec::new()));
+            let url = mock_obs(requests.clone()).await;
+            let sink = ObsSink::new(ObsConfig {
+                url,
+                password: Some("secret".into()),
+                scene: Some("Service".into()),
+                scene_item: Some("Lower Third".into()),
+                auto_hide_secs: Some(secs),
+                ..ObsConfig::default()
+            });
+
+            let passage = "John 3:16 — For God so loved the world.";
+            let presentation =
+                Presentation::new("John 3:16", "KJV", passage, &PageLimits::default());
+            sink.show(&presentation).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let requests = requests.lock().unwrap().clone();
+            assert_eq!(requests.len(), 3, "auto_hide_secs {secs}");
+            assert_eq!(requests[2]["requestData"]["sceneItemEnabled"], true);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wrong_password() {
+        let url = mock_obs(Arc::new(StdMutex::new(Vec::new()))).await;
+        let sink = ObsSink::new(ObsConfig {
+            url,
+            password: Some("guess".into()),
+            ..ObsConfig::default()
+        });
+        assert!(sink.health().await.is_err());
+    }
+}
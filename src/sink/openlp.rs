@@ -0,0 +1,395 @@
+//! OpenLP over its v2 remote API: sends the verse live from the Bibles
+//! plugin in the spoken translation, or failing that as a custom slide
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Mutex as StdMutex;
+use tokio::sync::Mutex;
+
+use super::DisplaySink;
+use crate::output::Presentation;
+use crate::template::Template;
+
+/// Settings > Remote Interface in OpenLP
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OpenLpConfig {
+    pub url: String,
+    /// Only needed when the remote requires a login
+    pub username: String,
+    pub password: Option<String>,
+    /// Layout of the custom slide sent when no installed Bible has the verse
+    pub template: Template,
+}
+
+impl Default for OpenLpConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:4316".into(),
+            username: "openlp".into(),
+            password: None,
+            template: Template::default(),
+        }
+    }
+}
+
+/// Shows verses through OpenLP
+pub struct OpenLpSink {
+    config: OpenLpConfig,
+    http: reqwest::Client,
+    token: Mutex<Option<String>>,
+    /// The reference whose Bible passage is live, if any
+    triggered: StdMutex<Option<String>>,
+}
+
+impl OpenLpSink {
+    pub fn new(config: OpenLpConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+            token: Mutex::new(None),
+            triggered: StdMutex::new(None),
+        }
+    }
+
+    async fn login(&self, password: &str) -> Result<String> {
+        let reply: Value = self
+            .http
+            .post(format!("{}/api/v2/core/login", self.config.url))
+            .json(&json!({ "username": self.config.username, "password": password }))
+            .send()
+            .await
+            .with_context(|| format!("Not connected to OpenLP at {}", self.config.url))?
+            .error_for_status()
+            .context("OpenLP login")?
+            .json()
+            .await?;
+        match reply["token"].as_str() {
+            Some(token) => Ok(token.to_string()),
+            None => bail!("OpenLP login returned no token"),
+        }
+    }
+
+    /// Calls /api/v2/`path`, logging in first when a password is set and
+    /// again once if the token has expired
+    async fn call(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<Value>,
+    ) -> Result<Value> {
+        for retry in [false, true] {
+            let mut token = self.token.lock().await;
+            if let (None, Some(password)) = (token.as_ref(), &self.config.password) {
+                *token = Some(self.login(password).await?);
+            }
+            let mut request = self
+                .http
+                .request(method.clone(), format!("{}/api/v2/{path}", self.config.url));
+            if !query.is_empty() {
+                request = request.query(query);
+            }
+            if let Some(token) = token.as_ref() {
+                request = request.header("Authorization", token);
+            }
+            if let Some(body) = &body {
+                request = request.json(body);
+            }
+            let response = request
+                .send()
+                .await
+                .with_context(|| format!("Not connected to OpenLP at {}", self.config.url))?;
+            if response.status() == StatusCode::UNAUTHORIZED
+                && !retry
+                && self.config.password.is_some()
+            {
+                *token = None;
+                continue;
+            }
+            let response = response
+                .error_for_status()
+                .with_context(|| format!("OpenLP {path}"))?;
+            return Ok(response.json().await.unwrap_or(Value::Null));
+        }
+        bail!("OpenLP rejected the login")
+    }
+
+    /// The plugin's search results, each `[id, title, ...]`
+    async fn search(&self, plugin: &str, text: &str) -> Result<Vec<Value>> {
+        let path = format!("plugins/{plugin}/search");
+        let results = self
+            .call(Method::GET, &path, &[("text", text)], None)
+            .await?;
+        Ok(results.as_array().cloned().unwrap_or_default())
+    }
+
+    async fn go_live(&self, plugin: &str, id: Value) -> Result<()> {
+        let path = format!("plugins/{plugin}/live");
+        self.call(Method::POST, &path, &[], Some(json!({ "id": id })))
+            .await?;
+        self.set_display("show").await
+    }
+
+    /// The installed Bible named like `translation` ("KJV" picks "King James
+    /// Version (KJV)"), and the one OpenLP searches now
+    async fn find_bible(&self, translation: &str) -> Result<(Option<String>, Value)> {
+        let options = self.bible_options(Method::GET, None).await?;
+        let named = |name: &&str| {
+            name.split(|c: char| !c.is_alphanumeric())
+                .any(|word| word.eq_ignore_ascii_case(translation))
+        };
+        let bibles = options["options"].as_array().into_iter().flatten();
+        let bible = bibles.filter_map(Value::as_str).find(named);
+        Ok((bible.map(String::from), options["primary"].clone()))
+    }
+
+    async fn bible_options(&self, method: Method, bible: Option<&Value>) -> Result<Value> {
+        let body = bible.map(|bible| json!({ "option": bible }));
+        self.call(method, "plugins/bibles/search-options", &[], body)
+            .await
+    }
+
+    /// Send the verse live from the Bible in its translation (the primary
+    /// one for a verse without); false when that Bible isn't installed or
+    /// lacks the verse
+    async fn bible_display(&self, presentation: &Presentation) -> Result<bool> {
+        let (reference, translation) = (&presentation.reference, &presentation.translation);
+        if translation.is_empty() {
+            return self.bible_live(reference).await;
+        }
+        let (Some(bible), primary) = self.find_bible(translation).await? else {
+            eprintln!("❌ OpenLP has no {translation} Bible, sending {reference} as a slide");
+            return Ok(false);
+        };
+        if primary == bible {
+            return self.bible_live(reference).await;
+        }
+        // Switch only for this verse; the primary Bible is the operator's
+        self.bible_options(Method::POST, Some(&json!(bible)))
+            .await?;
+        let shown = self.bible_live(reference).await;
+        self.bible_options(Method::POST, Some(&primary)).await?;
+        shown
+    }
+
+    /// Search the selected Bible and send `reference` live; false when it
+    /// has no such verse
+    async fn bible_live(&self, reference: &str) -> Result<bool> {
+        if self.search("bibles", reference).await?.is_empty() {
+            return Ok(false);
+        }
+        // The Bibles plugin takes the reference itself as the id
+        self.go_live("bibles", json!(reference)).await?;
+        Ok(true)
+    }
+
+    /// Send one page of text live as a custom slide
+    async fn slide_display(&self, title: &str, text: &str) -> Result<()> {
+        let body = json!({ "title": title, "text": text });
+        self.call(Method::POST, "plugins/custom/slide", &[], Some(body))
+            .await?;
+        self.set_display("show").await
+    }
+
+    async fn set_display(&self, display: &str) -> Result<()> {
+        let body = json!({ "display": display });
+        self.call(Method::POST, "core/display", &[], Some(body))
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DisplaySink for OpenLpSink {
+    fn name(&self) -> &str {
+        "OpenLP"
+    }
+
+    async fn show(&self, presentation: &Presentation) -> Result<()> {
+        let reference = &presentation.reference;
+        // OpenLP steps a live Bible passage's own slides
+        if self.triggered.lock().unwrap().as_ref() == Some(reference) {
+            return Ok(());
+        }
+        if presentation.pager.index() == 0 && self.bible_display(presentation).await? {
+            *self.triggered.lock().unwrap() = Some(reference.clone());
+            return Ok(());
+        }
+        *self.triggered.lock().unwrap() = None;
+        let text = presentation
+            .render(&self.config.template)
+            .ok_or_else(|| anyhow!("Nothing to show for {reference}"))?;
+        self.slide_display(reference, &text).await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        *self.triggered.lock().unwrap() = None;
+        self.set_display("blank").await
+    }
+
+    async fn health(&self) -> Result<()> {
+        self.call(Method::GET, "core/system", &[], None).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paginate::PageLimits;
+    use crate::sink::mock::{mock_http, presentation, HttpRequest, Recorder};
+
+    /// A stand-in OpenLP remote with three Bibles, of which only John 3:16
+    /// is found, that wants the token "abc" when `password` is set
+    async fn mock_openlp(password: Option<&'static str>) -> (String, Recorder<HttpRequest>) {
+        mock_http(move |request| {
+            let authorized = password.is_none()
+                || request.headers.get("authorization").map(String::as_str) == Some("abc");
+            let path = request.path.as_str();
+            match (request.method.as_str(), path, request.param("text")) {
+                ("POST", "/api/v2/core/login", _) if request.body["password"] == json!(password) => {
+                    (200, json!({ "token": "abc" }))
+                }
+                (_, "/api/v2/core/login", _) => (401, Value::Null),
+                _ if !authorized => (401, Value::Null),
+                ("GET", "/api/v2/plugins/bibles/search-options", _) => (
+                    200,
+                    json!({ "primary": "AKJV", "options": ["AKJV", "King James Version (KJV)", "WEB"] }),
+                ),
+                (_, "/api/v2/plugins/bibles/search", "John 3:16") => {
+                    (200, json!([["John 3:16", "For God so loved..."]]))
+                }
+                (_, p, _) if p.ends_with("/search") => (200, json!([])),
+                _ => (204, Value::Null),
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_bible_or_custom_slide() {
+        let (url, requests) = mock_openlp(None).await;
+        let sink = OpenLpSink::new(OpenLpConfig {
+            url,
+            template: Template::new("{text} {counter}"),
+            ..OpenLpConfig::default()
+        });
+
+        let mut john = presentation("John 3:16", "For God so loved the world.");
+        sink.show(&john).await.unwrap();
+        // OpenLP steps its own slides for a live Bible passage
+        john.pager.next_page();
+        sink.show(&john).await.unwrap();
+
+        let limits = PageLimits {
+            max_chars: 40,
+            ..PageLimits::default()
+        };
+        let passage = "Jude 1:24 — Now unto him that is able.\nJude 1:25 — To the only wise God.";
+        let mut jude = Presentation::new("Jude 1:24-25", "WEB", passage, &limits);
+        sink.show(&jude).await.unwrap();
+        jude.pager.next_page();
+        sink.show(&jude).await.unwrap();
+        // No NIV installed, so not the primary AKJV either
+        let psalm = "Psalm 23:1 — The Lord is my shepherd.";
+        let psalm = Presentation::new("Psalm 23:1", "NIV", psalm, &limits);
+        sink.show(&psalm).await.unwrap();
+        sink.clear().await.unwrap();
+
+        let calls = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| (format!("{} {}", r.method, r.path), r.body.clone()))
+            .collect::<Vec<_>>();
+        let call = |request: &str, body: Value| (request.to_string(), body);
+        let options = "/api/v2/plugins/bibles/search-options";
+        let show = json!({ "display": "show" });
+        assert_eq!(
+            calls,
+            [
+                call(&format!("GET {options}"), Value::Null),
+                call(
+                    &format!("POST {options}"),
+                    json!({ "option": "King James Version (KJV)" })
+                ),
+                call("GET /api/v2/plugins/bibles/search", Value::Null),
+                call(
+                    "POST /api/v2/plugins/bibles/live",
+                    json!({ "id": "John 3:16" })
+                ),
+                call("POST /api/v2/core/display", show.clone()),
+                call(&format!("POST {options}"), json!({ "option": "AKJV" })),
+                call(&format!("GET {options}"), Value::Null),
+                call(&format!("POST {options}"), json!({ "option": "WEB" })),
+                call("GET /api/v2/plugins/bibles/search", Value::Null),
+                call(&format!("POST {options}"), json!({ "option": "AKJV" })),
+                call(
+                    "POST /api/v2/plugins/custom/slide",
+                    json!({ "title": "Jude 1:24-25", "text": "Jude 1:24 — Now unto him that is able. (1/2)" })
+                ),
+                call("POST /api/v2/core/display", show.clone()),
+                call(
+                    "POST /api/v2/plugins/custom/slide",
+                    json!({ "title": "Jude 1:24-25", "text": "Jude 1:25 — To the only wise God. (2/2)" })
+                ),
+                call("POST /api/v2/core/display", show.clone()),
+                call(&format!("GET {options}"), Value::Null),
+                call(
+                    "POST /api/v2/plugins/custom/slide",
+                    json!({ "title": "Psalm 23:1", "text": "Psalm 23:1 — The Lord is my shepherd." })
+                ),
+                call("POST /api/v2/core/display", show),
+                call("POST /api/v2/core/display", json!({ "display": "blank" })),
+            ]
+        );
+
+        let error = sink.show(&Presentation::default()).await.unwrap_err();
+        assert_eq!(error.to_string(), "Nothing to show for ");
+    }
+
+    #[tokio::test]
+    async fn test_login() {
+        let (url, requests) = mock_openlp(Some("secret")).await;
+        let sink = OpenLpSink::new(OpenLpConfig {
+            url: url.clone(),
+            password: Some("secret".into()),
+            ..OpenLpConfig::default()
+        });
+        sink.health().await.unwrap();
+        // An expired token logs in again
+        *sink.token.lock().await = Some("expired".into());
+        sink.health().await.unwrap();
+
+        let paths = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| (r.path.clone(), r.headers.get("authorization").cloned()))
+            .collect::<Vec<_>>();
+        let system = "/api/v2/core/system".to_string();
+        let login = "/api/v2/core/login".to_string();
+        assert_eq!(
+            paths,
+            [
+                (login.clone(), None),
+                (system.clone(), Some("abc".into())),
+                (system.clone(), Some("expired".into())),
+                (login, None),
+                (system, Some("abc".into())),
+            ]
+        );
+
+        let sink = OpenLpSink::new(OpenLpConfig {
+            url,
+            password: Some("wrong".into()),
+            ..OpenLpConfig::default()
+        });
+        let error = sink.health().await.unwrap_err();
+        assert_eq!(error.to_string(), "OpenLP login");
+    }
+}
@@ -0,0 +1,196 @@
+//! OSC over UDP, so Resolume, QLab and lighting consoles can react to
+//! scripture moments
+
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use tokio::net::UdpSocket;
+use tokio::sync::OnceCell;
+
+use super::DisplaySink;
+use crate::output::Presentation;
+use crate::template::{Template, VerseNumbers};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OscConfig {
+    /// "host:port" of every receiver
+    pub targets: Vec<String>,
+    pub reference_address: String,
+    pub text_address: String,
+    pub clear_address: String,
+    pub template: Template,
+}
+
+impl Default for OscConfig {
+    fn default() -> Self {
+        Self {
+            targets: vec!["127.0.0.1:7000".into()],
+            reference_address: "/easysermon/reference".into(),
+            text_address: "/easysermon/text".into(),
+            clear_address: "/easysermon/clear".into(),
+            // The reference has its own message
+            template: Template::default().with_verse_numbers(VerseNumbers::None),
+        }
+    }
+}
+
+/// OSC string: the bytes, a NUL, then NULs up to a multiple of four
+fn push_string(packet: &mut Vec<u8>, s: &str) {
+    packet.extend_from_slice(s.as_bytes());
+    packet.push(0);
+    while !packet.len().is_multiple_of(4) {
+        packet.push(0);
+    }
+}
+
+/// An OSC 1.0 message with string arguments
+pub fn encode(address: &str, args: &[&str]) -> Vec<u8> {
+    let mut packet = Vec::new();
+    push_string(&mut packet, address);
+    push_string(&mut packet, &format!(",{}", "s".repeat(args.len())));
+    for arg in args {
+        push_string(&mut packet, arg);
+    }
+    packet
+}
+
+/// Sends each verse to every OSC target
+pub struct OscSink {
+    config: OscConfig,
+    /// One socket per address family, bound on first use
+    v4: OnceCell<UdpSocket>,
+    v6: OnceCell<UdpSocket>,
+}
+
+impl OscSink {
+    pub fn new(config: OscConfig) -> Self {
+        Self {
+            config,
+            v4: OnceCell::new(),
+            v6: OnceCell::new(),
+        }
+    }
+
+    /// A socket that can reach `target`
+    async fn socket(&self, target: &SocketAddr) -> Result<&UdpSocket> {
+        let socket = if target.is_ipv4() {
+            self.v4
+                .get_or_try_init(|| UdpSocket::bind("0.0.0.0:0"))
+                .await
+        } else {
+            self.v6.get_or_try_init(|| UdpSocket::bind("[::]:0")).await
+        };
+        Ok(socket?)
+    }
+
+    async fn send_to(&self, target: &str, packets: &[Vec<u8>]) -> Result<()> {
+        let Some(addr) = tokio::net::lookup_host(target).await?.next() else {
+            bail!("no address");
+        };
+        let socket = self.socket(&addr).await?;
+        for packet in packets {
+            socket.send_to(packet, addr).await?;
+        }
+        Ok(())
+    }
+
+    /// Send to every target, even after one fails
+    async fn send(&self, packets: &[Vec<u8>]) -> Result<()> {
+        let mut failed = Vec::new();
+        for target in &self.config.targets {
+            if let Err(e) = self.send_to(target, packets).await {
+                failed.push(format!("{target}: {e}"));
+            }
+        }
+        if !failed.is_empty() {
+            bail!("OSC {}", failed.join(", "));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DisplaySink for OscSink {
+    fn name(&self) -> &str {
+        "OSC"
+    }
+
+    async fn show(&self, presentation: &Presentation) -> Result<()> {
+        let text = presentation
+            .render(&self.config.template)
+            .ok_or_else(|| anyhow!("Nothing to show for {}", presentation.reference))?;
+        self.send(&[
+            encode(&self.config.reference_address, &[&presentation.reference]),
+            encode(&self.config.text_address, &[&text]),
+        ])
+        .await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.send(&[encode(&self.config.clear_address, &[])]).await
+    }
+
+    /// UDP has no connection to check; just that there is somewhere to send
+    async fn health(&self) -> Result<()> {
+        if self.config.targets.is_empty() {
+            bail!("No OSC targets");
+        }
+        for target in &self.config.targets {
+            if tokio::net::lookup_host(target).await?.next().is_none() {
+                bail!("OSC target {target} has no address");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paginate::PageLimits;
+
+    async fn receive(receiver: &UdpSocket, count: usize) -> Vec<Vec<u8>> {
+        let mut buf = [0; 512];
+        let mut received = Vec::new();
+        for _ in 0..count {
+            let n = receiver.recv(&mut buf).await.unwrap();
+            received.push(buf[..n].to_vec());
+        }
+        received
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("/a", &["hi"]), b"/a\0\0,s\0\0hi\0\0");
+        assert_eq!(encode("/clear", &[]), b"/clear\0\0,\0\0\0");
+    }
+
+    #[tokio::test]
+    async fn test_show_and_clear() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let v6_receiver = UdpSocket::bind("[::1]:0").await.unwrap();
+        let sink = OscSink::new(OscConfig {
+            targets: vec![
+                receiver.local_addr().unwrap().to_string(),
+                v6_receiver.local_addr().unwrap().to_string(),
+            ],
+            reference_address: "/scripture/ref".into(),
+            ..OscConfig::default()
+        });
+
+        let passage = "John 3:16 — For God so loved the world.";
+        let presentation = Presentation::new("John 3:16", "KJV", passage, &PageLimits::default());
+        sink.show(&presentation).await.unwrap();
+        sink.clear().await.unwrap();
+
+        let received = receive(&receiver, 3).await;
+        assert_eq!(received[0], encode("/scripture/ref", &["John 3:16"]));
+        let text = "For God so loved the world.";
+        assert_eq!(received[1], encode("/easysermon/text", &[text]));
+        assert_eq!(received[2], encode("/easysermon/clear", &[]));
+        // IPv6 targets get the same messages
+        assert_eq!(receive(&v6_receiver, 3).await, received);
+    }
+}
@@ -0,0 +1,64 @@
+//! ProPresenter as a sink: stage and audience messages per `Routing`, or a
+//! pre-built library presentation
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+use super::DisplaySink;
+use crate::output::{clear_display, page_display, presentation_display, Presentation, Routing};
+use crate::propresenter::ProPresenter;
+
+pub struct ProPresenterSink {
+    client: ProPresenter,
+    routing: Routing,
+    /// The reference whose library presentation is live, if any
+    triggered: Mutex<Option<String>>,
+}
+
+impl ProPresenterSink {
+    pub fn new(client: ProPresenter, routing: Routing) -> Self {
+        Self {
+            client,
+            routing,
+            triggered: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl DisplaySink for ProPresenterSink {
+    fn name(&self) -> &str {
+        "ProPresenter"
+    }
+
+    async fn show(&self, presentation: &Presentation) -> Result<()> {
+        let reference = &presentation.reference;
+        if self.routing.presentations {
+            // ProPresenter steps a triggered presentation's own slides
+            if self.triggered.lock().unwrap().as_ref() == Some(reference) {
+                return Ok(());
+            }
+            if presentation.pager.index() == 0
+                && presentation_display(&self.client, &self.routing, reference).await
+            {
+                *self.triggered.lock().unwrap() = Some(reference.clone());
+                return Ok(());
+            }
+        }
+        *self.triggered.lock().unwrap() = None;
+        page_display(&self.client, &self.routing, presentation).await
+    }
+
+    async fn clear(&self) -> Result<()> {
+        *self.triggered.lock().unwrap() = None;
+        clear_display(&self.client, &self.routing).await
+    }
+
+    async fn health(&self) -> Result<()> {
+        if !self.client.is_connected() {
+            bail!("Not connected to ProPresenter");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+//! vMix over its HTTP API: fills a title input's text fields and brings it
+//! in on an overlay channel
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::DisplaySink;
+use crate::output::Presentation;
+use crate::template::{Template, VerseNumbers};
+
+/// Settings > Web Controller in vMix
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VmixConfig {
+    pub url: String,
+    /// The title input's number, name or key
+    pub input: String,
+    /// Title fields, as listed under the input's Title Editor
+    pub reference_field: String,
+    pub text_field: String,
+    /// Overlay channel (1-4) to bring the title in and out on; `None` only
+    /// sets the text
+    pub overlay: Option<u8>,
+    pub template: Template,
+}
+
+impl Default for VmixConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:8088/api/".into(),
+            input: "Scripture".into(),
+            reference_field: "Reference.Text".into(),
+            text_field: "Text.Text".into(),
+            overlay: Some(1),
+            // The reference has its own field
+            template: Template::default().with_verse_numbers(VerseNumbers::None),
+        }
+    }
+}
+
+/// Shows verses on a vMix title
+pub struct VmixSink {
+    config: VmixConfig,
+    http: reqwest::Client,
+}
+
+impl VmixSink {
+    pub fn new(config: VmixConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// GET /api/?Function=...&Input=... plus `params`
+    async fn call(&self, function: &str, params: &[(&str, &str)]) -> Result<()> {
+        self.http
+            .get(&self.config.url)
+            .query(&[("Function", function), ("Input", &self.config.input)])
+            .query(params)
+            .send()
+            .await
+            .with_context(|| format!("Not connected to vMix at {}", self.config.url))?
+            .error_for_status()
+            .with_context(|| format!("vMix {function}"))?;
+        Ok(())
+    }
+
+    async fn set_text(&self, field: &str, value: &str) -> Result<()> {
+        let params = [("SelectedName", field), ("Value", value)];
+        self.call("SetText", &params).await
+    }
+
+    async fn overlay(&self, direction: &str) -> Result<()> {
+        match self.config.overlay {
+            Some(channel) => {
+                let function = format!("OverlayInput{channel}{direction}");
+                self.call(&function, &[]).await
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl DisplaySink for VmixSink {
+    fn name(&self) -> &str {
+        "vMix"
+    }
+
+    async fn show(&self, presentation: &Presentation) -> Result<()> {
+        let text = presentation
+            .render(&self.config.template)
+            .ok_or_else(|| anyhow!("Nothing to show for {}", presentation.reference))?;
+        let config = &self.config;
+        self.set_text(&config.reference_field, &presentation.reference)
+            .await?;
+        self.set_text(&config.text_field, &text).await?;
+        self.overlay("In").await
+    }
+
+    /// Takes the title out, or blanks it when there is no overlay channel
+    async fn clear(&self) -> Result<()> {
+        if self.config.overlay.is_some() {
+            return self.overlay("Out").await;
+        }
+        let config = &self.config;
+        self.set_text(&config.reference_field, "").await?;
+        self.set_text(&config.text_field, "").await
+    }
+
+    /// vMix answers a bare /api/ with its state
+    async fn health(&self) -> Result<()> {
+        self.http
+            .get(&self.config.url)
+            .send()
+            .await
+            .with_context(|| format!("Not connected to vMix at {}", self.config.url))?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::mock::{mock_http, presentation, HttpRequest};
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn test_set_text_and_overlay() {
+        let (url, requests) = mock_http(|_| (200, Value::Null)).await;
+        let sink = VmixSink::new(VmixConfig {
+            url: format!("{url}/api/"),
+            input: "3".into(),
+            overlay: Some(2),
+            template: Template::new("{text} {counter}").with_verse_numbers(VerseNumbers::None),
+            ..VmixConfig::default()
+        });
+
+        let shown = presentation("John 3:16", "For God so loved the world & all in it.");
+        sink.show(&shown).await.unwrap();
+        sink.clear().await.unwrap();
+
+        let requests: Vec<HttpRequest> = requests.lock().unwrap().clone();
+        let call = |i: usize, key: &str| requests[i].param(key).to_string();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/api/");
+        assert_eq!(call(0, "Function"), "SetText");
+        assert_eq!(call(0, "Input"), "3");
+        assert_eq!(call(0, "SelectedName"), "Reference.Text");
+        assert_eq!(call(0, "Value"), "John 3:16");
+        assert_eq!(call(1, "SelectedName"), "Text.Text");
+        assert_eq!(call(1, "Value"), "For God so loved the world & all in it.");
+        assert_eq!(call(2, "Function"), "OverlayInput2In");
+        assert_eq!(call(3, "Function"), "OverlayInput2Out");
+        assert_eq!(call(3, "Input"), "3");
+    }
+
+    #[tokio::test]
+    async fn test_without_overlay() {
+        let (url, requests) = mock_http(|_| (200, Value::Null)).await;
+        let sink = VmixSink::new(VmixConfig {
+            url,
+            overlay: None,
+            ..VmixConfig::default()
+        });
+
+        let error = sink.show(&Presentation::default()).await.unwrap_err();
+        assert_eq!(error.to_string(), "Nothing to show for ");
+        sink.show(&presentation("John 3:16", "For God..."))
+            .await
+            .unwrap();
+        sink.clear().await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let calls = requests
+            .iter()
+            .map(|r| {
+                (
+                    r.param("Function"),
+                    r.param("SelectedName"),
+                    r.param("Value"),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            calls,
+            [
+                ("SetText", "Reference.Text", "John 3:16"),
+                ("SetText", "Text.Text", "For God..."),
+                ("SetText", "Reference.Text", ""),
+                ("SetText", "Text.Text", ""),
+            ]
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Message templates, so each output shows the reference, text, translation
+//! and page counter in its own layout
+
+use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::paginate::{paginate, wrapped_lines, Page, PageLimits};
+
+/// "John 3:16 — For God so loved the world", as from `BibleStore::passage`
+static VERSE_LINE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^.+ \d+:(\d+) — (.*)$").unwrap());
+
+/// "()" or "[]" left by an empty placeholder, e.g. "({translation})"
+static EMPTY_GROUP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[ \t]*(?:\(\s*\)|\[\s*\])").unwrap());
+
+/// How each verse of a passage is numbered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VerseNumbers {
+    /// "John 3:16 — For God so loved the world"
+    #[default]
+    Reference,
+    /// "¹⁶ For God so loved the world"
+    Superscript,
+    /// "16 For God so loved the world"
+    Inline,
+    /// "For God so loved the world"
+    None,
+}
+
+impl FromStr for VerseNumbers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "reference" => Ok(VerseNumbers::Reference),
+            "superscript" => Ok(VerseNumbers::Superscript),
+            "inline" => Ok(VerseNumbers::Inline),
+            "none" => Ok(VerseNumbers::None),
+            _ => bail!(
+                "Unknown verse numbers: {s} (expected reference, superscript, inline or none)"
+            ),
+        }
+    }
+}
+
+fn superscript(n: &str) -> String {
+    n.chars()
+        .map(|c| match c {
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            c => c,
+        })
+        .collect()
+}
+
+/// Values for a template's placeholders
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fields<'a> {
+    pub reference: &'a str,
+    pub translation: &'a str,
+    pub text: &'a str,
+    pub page: usize,
+    pub pages: usize,
+}
+
+/// A message layout with `{reference}`, `{text}`, `{translation}`, `{page}`,
+/// `{pages}` and `{counter}` ("(1/2)", empty on a single page)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Template {
+    pub format: String,
+    pub verse_numbers: VerseNumbers,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        Self::new("{text}")
+    }
+}
+
+impl Template {
+    pub fn new(format: &str) -> Self {
+        Self {
+            format: format.to_string(),
+            verse_numbers: VerseNumbers::default(),
+        }
+    }
+
+    pub fn with_verse_numbers(mut self, verse_numbers: VerseNumbers) -> Self {
+        self.verse_numbers = verse_numbers;
+        self
+    }
+
+    /// Renumber each "Reference — text" line of a passage; other lines are kept
+    pub fn number_verses(&self, passage: &str) -> String {
+        if self.verse_numbers == VerseNumbers::Reference {
+            return passage.to_string();
+        }
+        passage
+            .lines()
+            .map(|line| match VERSE_LINE_RE.captures(line) {
+                Some(caps) => match self.verse_numbers {
+                    VerseNumbers::Superscript => format!("{} {}", superscript(&caps[1]), &caps[2]),
+                    VerseNumbers::Inline => format!("{} {}", &caps[1], &caps[2]),
+                    _ => caps[2].to_string(),
+                },
+                None => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn render(&self, fields: &Fields) -> String {
+        let counter = if fields.pages > 1 {
+            format!("({}/{})", fields.page, fields.pages)
+        } else {
+            String::new()
+        };
+        let frame = self
+            .format
+            .replace("{reference}", fields.reference)
+            .replace("{translation}", fields.translation)
+            .replace("{page}", &fields.page.to_string())
+            .replace("{pages}", &fields.pages.to_string())
+            .replace("{counter}", &counter);
+        EMPTY_GROUP_RE
+            .replace_all(&frame, "")
+            .replace("{text}", fields.text)
+            // An empty placeholder leaves no stray spaces behind
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+
+    /// Render one page cut by `paginate`, the `page`th of `pages`
+    pub fn render_page(
+        &self,
+        reference: &str,
+        translation: &str,
+        text: &str,
+        page: usize,
+        pages: usize,
+    ) -> String {
+        self.render(&Fields {
+            reference,
+            translation,
+            text: &self.number_verses(text),
+            page,
+            pages,
+        })
+    }
+
+    /// What is left of `limits` for the passage once the template's other
+    /// lines and characters are in; the counter is taken at its widest
+    fn text_limits(&self, reference: &str, translation: &str, limits: &PageLimits) -> PageLimits {
+        const TEXT: &str = "\u{1}";
+        let frame = self.render(&Fields {
+            reference,
+            translation,
+            text: TEXT,
+            page: 99,
+            pages: 99,
+        });
+        let lines: usize = frame
+            .lines()
+            .filter(|line| !line.contains(TEXT))
+            .map(|line| wrapped_lines(line, limits.line_width))
+            .sum();
+        let chars = frame.chars().count() - usize::from(frame.contains(TEXT));
+        PageLimits {
+            max_chars: limits.max_chars.saturating_sub(chars).max(1),
+            max_lines: limits.max_lines.saturating_sub(lines).max(1),
+            ..*limits
+        }
+    }
+
+    /// Paginate a passage and render each page as a finished message that
+    /// stays within `limits`, header and counter included
+    pub fn pages(
+        &self,
+        reference: &str,
+        translation: &str,
+        passage: &str,
+        limits: &PageLimits,
+    ) -> Vec<Page> {
+        let limits = self.text_limits(reference, translation, limits);
+        // Measure the verses as they will be shown
+        let pages = paginate(reference, &self.number_verses(passage), &limits);
+        let total = pages.len();
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, page)| Page {
+                text: self.render(&Fields {
+                    reference,
+                    translation,
+                    text: &page.text,
+                    page: i + 1,
+                    pages: total,
+                }),
+                label: page.label,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSAGE: &str = "John 3:16 — For God so loved the world.\n\
+                           John 3:17 — For God did not send his Son to condemn.";
+
+    #[test]
+    fn test_verse_numbers() {
+        let numbered = |n: VerseNumbers| {
+            Template::default()
+                .with_verse_numbers(n)
+                .number_verses(PASSAGE)
+        };
+
+        assert_eq!(numbered(VerseNumbers::Reference), PASSAGE);
+        assert!(numbered(VerseNumbers::Superscript).starts_with("¹⁶ For God so loved"));
+        assert!(numbered(VerseNumbers::Inline)
+            .ends_with("\n17 For God did not send his Son to condemn."));
+        assert!(numbered(VerseNumbers::None).starts_with("For God so loved"));
+        assert_eq!(
+            "Superscript".parse::<VerseNumbers>().unwrap(),
+            VerseNumbers::Superscript
+        );
+        assert!("roman".parse::<VerseNumbers>().is_err());
+    }
+
+    #[test]
+    fn test_render_pages() {
+        let template = Template::new("{reference} ({translation}) {counter}\n{text}")
+            .with_verse_numbers(VerseNumbers::Superscript);
+        let limits = PageLimits {
+            max_chars: 80,
+            ..PageLimits::default()
+        };
+
+        let pages = template.pages("John 3:16-17", "KJV", PASSAGE, &limits);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].label, "John 3:16-17 (1/2)");
+        assert_eq!(
+            pages[0].text,
+            "John 3:16-17 (KJV) (1/2)\n¹⁶ For God so loved the world."
+        );
+
+        let single = Template::new("{reference} {counter}\n{text}").render(&Fields {
+            reference: "John 3:16",
+            text: "For God so loved the world.",
+            pages: 1,
+            page: 1,
+            ..Fields::default()
+        });
+        assert_eq!(single, "John 3:16\nFor God so loved the world.");
+
+        let untranslated = Template::new("{reference} ({translation}) {counter}").render(&Fields {
+            reference: "John 3:16",
+            pages: 1,
+            ..Fields::default()
+        });
+        assert_eq!(untranslated, "John 3:16");
+    }
+
+    #[test]
+    fn test_pages_leave_room_for_the_header() {
+        let template = Template::new("{reference} ({translation}) {counter}\n{text}");
+        let limits = PageLimits {
+            max_chars: 64,
+            max_lines: 3,
+            line_width: 0,
+        };
+        // Each verse fits the limits alone, but not under the header
+        let passage = "Ps 23:1 — The Lord is my shepherd; I shall not want.\n\
+                       Ps 23:2 — He maketh me to lie down in green pastures.";
+
+        let pages = template.pages("Psalm 23:1-2", "KJV", passage, &limits);
+        for page in &pages {
+            assert!(
+                page.text.chars().count() <= limits.max_chars,
+                "{}",
+                page.text
+            );
+            assert!(
+                page.text.lines().count() <= limits.max_lines,
+                "{}",
+                page.text
+            );
+        }
+        assert_eq!(pages.len(), 4);
+        assert_eq!(
+            pages[0].text,
+            "Psalm 23:1-2 (KJV) (1/4)\nPs 23:1 — The Lord is my shepherd;"
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use anyhow::{anyhow, Context, Result};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::bible::BibleStore;
+use crate::search::SearchIndex;
+use crate::versification::Versification;
+
+pub const TRANSLATIONS_DIR: &str = "translations";
+pub const DEFAULT_TRANSLATION: &str = "AKJV";
+
+/// `translations/<abbr>/metadata.json`; every field is optional
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct TranslationMeta {
+    pub name: String,
+    pub abbreviation: String,
+    pub language: String,
+    pub copyright: Option<String>,
+    /// Chapter and verse numbering the text follows, e.g. "kjv"
+    pub versification: String,
+}
+
+#[derive(Debug)]
+struct Translation {
+    meta: TranslationMeta,
+    path: PathBuf,
+    store: OnceCell<Arc<BibleStore>>,
+    index: OnceCell<Arc<SearchIndex>>,
+}
+
+/// Installed translations, each parsed on first use. Shared through `Arc`,
+/// so the default can be switched while the pipeline is running.
+#[derive(Debug)]
+pub struct TranslationRegistry {
+    translations: Vec<Translation>,
+    default: RwLock<String>,
+}
+
+impl TranslationRegistry {
+    /// Every `<dir>/<abbr>/<abbr>.json`, with its metadata.json when present;
+    /// a translation whose metadata.json is unreadable is skipped
+    pub fn scan(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut entries = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_dir())
+            .collect::<Vec<_>>();
+        entries.sort();
+
+        let mut translations = Vec::new();
+        for entry in entries {
+            let id = entry.file_name().unwrap_or_default().to_string_lossy();
+            let path = entry.join(format!("{id}.json"));
+            if !path.exists() {
+                continue;
+            }
+
+            let meta_path = entry.join("metadata.json");
+            let mut meta: TranslationMeta = if meta_path.exists() {
+                // One broken translation shouldn't take the others down
+                let meta = fs::read_to_string(&meta_path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|data| Ok(serde_json::from_str(&data)?));
+                match meta {
+                    Ok(meta) => meta,
+                    Err(e) => {
+                        eprintln!("❌ Skipping {}: {e}", meta_path.display());
+                        continue;
+                    }
+                }
+            } else {
+                TranslationMeta::default()
+            };
+            if meta.abbreviation.is_empty() {
+                meta.abbreviation = id.to_uppercase();
+            }
+            if meta.name.is_empty() {
+                meta.name = meta.abbreviation.clone();
+            }
+            if meta.language.is_empty() {
+                meta.language = "en".into();
+            }
+            if meta.versification.is_empty() {
+                meta.versification = "kjv".into();
+            }
+
+            translations.push(Translation {
+                meta,
+                path,
+                store: OnceCell::new(),
+                index: OnceCell::new(),
+            });
+        }
+
+        // AKJV when installed, otherwise whatever comes first
+        let default = translations
+            .iter()
+            .map(|t| &t.meta.abbreviation)
+            .find(|a| a.eq_ignore_ascii_case(DEFAULT_TRANSLATION))
+            .or_else(|| translations.first().map(|t| &t.meta.abbreviation))
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_TRANSLATION.into());
+
+        Ok(Self {
+            translations,
+            default: RwLock::new(default),
+        })
+    }
+
+    fn find(&self, abbreviation: &str) -> Option<&Translation> {
+        self.translations
+            .iter()
+            .find(|t| t.meta.abbreviation.eq_ignore_ascii_case(abbreviation))
+    }
+
+    pub fn list(&self) -> Vec<&TranslationMeta> {
+        self.translations.iter().map(|t| &t.meta).collect()
+    }
+
+    pub fn meta(&self, abbreviation: &str) -> Option<&TranslationMeta> {
+        self.find(abbreviation).map(|t| &t.meta)
+    }
+
+    /// The text of an installed translation, e.g. "NIV"
+    pub fn get(&self, abbreviation: &str) -> Result<Arc<BibleStore>> {
+        let t = self
+            .find(abbreviation)
+            .ok_or_else(|| anyhow!("Translation not installed: {abbreviation}"))?;
+        t.store
+            .get_or_try_init(|| {
+                let path = t.path.to_string_lossy();
+                let versification = Versification::from_name(&t.meta.versification);
+                Ok(Arc::new(
+                    BibleStore::load(&path)?.with_versification(versification),
+                ))
+            })
+            .cloned()
+    }
+
+    /// Full-text index of a translation, built on first search
+    pub fn search_index(&self, abbreviation: &str) -> Result<Arc<SearchIndex>> {
+        let t = self
+            .find(abbreviation)
+            .ok_or_else(|| anyhow!("Translation not installed: {abbreviation}"))?;
+        let bible = self.get(abbreviation)?;
+        Ok(t.index
+            .get_or_init(|| Arc::new(SearchIndex::build(&bible)))
+            .clone())
+    }
+
+    pub fn default_translation(&self) -> String {
+        self.default.read().unwrap().clone()
+    }
+
+    pub fn set_default(&self, abbreviation: &str) -> Result<()> {
+        let t = self
+            .find(abbreviation)
+            .ok_or_else(|| anyhow!("Translation not installed: {abbreviation}"))?;
+        *self.default.write().unwrap() = t.meta.abbreviation.clone();
+        Ok(())
+    }
+
+    /// The requested translation when installed, otherwise the default
+    pub fn resolve(&self, requested: Option<&str>) -> Result<Arc<BibleStore>> {
+        self.get(&self.resolve_name(requested))
+    }
+
+    /// Abbreviation of the translation `resolve` picks, e.g. "KJV"
+    pub fn resolve_name(&self, requested: Option<&str>) -> String {
+        match requested.and_then(|r| self.find(r)) {
+            Some(t) => t.meta.abbreviation.clone(),
+            None => self.default_translation(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_lists_and_switches_translations() {
+        let registry = TranslationRegistry::scan("testdata/translations").unwrap();
+
+        let names = registry
+            .list()
+            .iter()
+            .map(|m| m.abbreviation.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["KJV", "LUT", "WEB"]);
+
+        let web = registry.meta("web").unwrap();
+        assert_eq!(web.name, "World English Bible");
+        assert_eq!(web.language, "en");
+        assert_eq!(web.versification, "kjv");
+
+        // No AKJV installed, so the first translation is the default
+        assert_eq!(registry.default_translation(), "KJV");
+        assert_eq!(registry.resolve_name(Some("web")), "WEB");
+        let kjv = registry.resolve(Some("NOPE")).unwrap();
+        assert!(kjv.passage("John 3:16").contains("only begotten Son"));
+        assert!(Arc::ptr_eq(&kjv, &registry.get("kjv").unwrap()));
+        let index = registry.search_index("KJV").unwrap();
+        assert_eq!(index.search("only begotten", 5)[0].reference, "John 3:16");
+        assert!(Arc::ptr_eq(&index, &registry.search_index("kjv").unwrap()));
+
+        registry.set_default("web").unwrap();
+        let web = registry.resolve(None).unwrap();
+        assert!(web.passage("John 3:16").contains("one and only Son"));
+        assert!(registry.set_default("NIV").is_err());
+        assert_eq!(registry.default_translation(), "WEB");
+    }
+
+    #[test]
+    fn test_malformed_metadata_skips_only_that_translation() {
+        let dir = std::env::temp_dir().join(format!("easy_sermon_scan_{}", std::process::id()));
+        for id in ["kjv", "web"] {
+            let translation = dir.join(id);
+            fs::create_dir_all(&translation).unwrap();
+            let source = format!("testdata/translations/{id}/{id}.json");
+            fs::copy(source, translation.join(format!("{id}.json"))).unwrap();
+        }
+        fs::write(dir.join("web/metadata.json"), "{ not json").unwrap();
+
+        let registry = TranslationRegistry::scan(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        let names = registry
+            .unwrap()
+            .list()
+            .iter()
+            .map(|m| m.abbreviation.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["KJV"]);
+    }
+}
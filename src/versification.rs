@@ -0,0 +1,226 @@
+//! Chapter and verse numbering schemes. References are detected in the KJV
+//! (English) numbering and mapped into a translation's own scheme for lookup.
+
+/// `versification` in a translation's metadata.json
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Versification {
+    /// English Bibles: KJV, NKJV, ESV, NIV, RVR, ...
+    #[default]
+    Kjv,
+    /// Masoretic (BHS) chapter breaks, e.g. Luther, Elberfelder, JPS
+    Hebrew,
+}
+
+impl Versification {
+    /// Unknown names fall back to KJV numbering
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "hebrew" | "masoretic" | "bhs" | "org" | "luther" => Versification::Hebrew,
+            _ => Versification::Kjv,
+        }
+    }
+
+    fn shifts(self) -> &'static [Shift] {
+        match self {
+            Versification::Kjv => &[],
+            Versification::Hebrew => HEBREW,
+        }
+    }
+
+    /// Where KJV `book chapter:verse` is found in this scheme
+    pub fn map(self, book: &str, chapter: u32, verse: u32) -> (u32, u32) {
+        if self == Versification::Kjv {
+            return (chapter, verse);
+        }
+        if book == "Psalm" {
+            return (chapter, verse + psalm_title_verses(chapter));
+        }
+        self.shifts()
+            .iter()
+            .find(|s| s.book == book && s.chapter == chapter && (s.from..=s.to).contains(&verse))
+            .map(|s| (s.to_chapter, s.to_verse + verse - s.from))
+            .unwrap_or((chapter, verse))
+    }
+
+    /// The KJV numbering of `book chapter:verse` in this scheme; `None` for
+    /// verses KJV has no number for, such as Hebrew psalm titles
+    pub fn to_kjv(self, book: &str, chapter: u32, verse: u32) -> Option<(u32, u32)> {
+        if self == Versification::Kjv {
+            return Some((chapter, verse));
+        }
+        if book == "Psalm" {
+            let titles = psalm_title_verses(chapter);
+            return (verse > titles).then(|| (chapter, verse - titles));
+        }
+        let shift = self.shifts().iter().find(|s| {
+            s.book == book
+                && s.to_chapter == chapter
+                && (s.to_verse..=s.to_verse + s.to - s.from).contains(&verse)
+        });
+        match shift {
+            Some(s) => Some((s.chapter, s.from + verse - s.to_verse)),
+            // Only the shifted verses moved chapter; the rest keep their numbers
+            None => Some((chapter, verse)),
+        }
+    }
+
+    /// Convert `book chapter:verse` from one scheme to another
+    pub fn convert(
+        from: Versification,
+        to: Versification,
+        book: &str,
+        chapter: u32,
+        verse: u32,
+    ) -> Option<(u32, u32)> {
+        let (chapter, verse) = from.to_kjv(book, chapter, verse)?;
+        Some(to.map(book, chapter, verse))
+    }
+}
+
+/// Hebrew psalms count their superscription as verse 1 (or 1-2)
+fn psalm_title_verses(psalm: u32) -> u32 {
+    if PSALM_TWO_VERSE_TITLES.contains(&psalm) {
+        2
+    } else if PSALM_ONE_VERSE_TITLES.contains(&psalm) {
+        1
+    } else {
+        0
+    }
+}
+
+/// KJV `book chapter:from-to` is numbered `to_chapter:to_verse…` instead
+struct Shift {
+    book: &'static str,
+    chapter: u32,
+    from: u32,
+    to: u32,
+    to_chapter: u32,
+    to_verse: u32,
+}
+
+const fn shift(
+    book: &'static str,
+    chapter: u32,
+    (from, to): (u32, u32),
+    (to_chapter, to_verse): (u32, u32),
+) -> Shift {
+    Shift {
+        book,
+        chapter,
+        from,
+        to,
+        to_chapter,
+        to_verse,
+    }
+}
+
+#[rustfmt::skip]
+const PSALM_ONE_VERSE_TITLES: &[u32] = &[
+    3, 4, 5, 6, 7, 8, 9, 12, 13, 18, 19, 20, 21, 22, 30, 31, 34, 36, 38, 39, 40, 41, 42, 44, 45,
+    46, 47, 48, 49, 53, 55, 56, 57, 58, 59, 61, 62, 63, 64, 65, 67, 68, 69, 70, 75, 76, 77, 80,
+    81, 83, 84, 85, 88, 89, 92, 102, 108, 140, 142,
+];
+
+const PSALM_TWO_VERSE_TITLES: &[u32] = &[51, 52, 54, 60];
+
+/// Where the Masoretic text breaks chapters differently from the KJV
+#[rustfmt::skip]
+const HEBREW: &[Shift] = &[
+    shift("Genesis", 31, (55, 55), (32, 1)),
+    shift("Genesis", 32, (1, 32), (32, 2)),
+    shift("Exodus", 8, (1, 4), (7, 26)),
+    shift("Exodus", 8, (5, 32), (8, 1)),
+    shift("Exodus", 22, (1, 1), (21, 37)),
+    shift("Exodus", 22, (2, 31), (22, 1)),
+    shift("Leviticus", 6, (1, 7), (5, 20)),
+    shift("Leviticus", 6, (8, 30), (6, 1)),
+    shift("Numbers", 16, (36, 50), (17, 1)),
+    shift("Numbers", 17, (1, 13), (17, 16)),
+    shift("Deuteronomy", 12, (32, 32), (13, 1)),
+    shift("Deuteronomy", 13, (1, 18), (13, 2)),
+    shift("2 Samuel", 18, (33, 33), (19, 1)),
+    shift("2 Samuel", 19, (1, 43), (19, 2)),
+    shift("1 Kings", 4, (21, 34), (5, 1)),
+    shift("1 Kings", 5, (1, 18), (5, 15)),
+    shift("2 Kings", 11, (21, 21), (12, 1)),
+    shift("2 Kings", 12, (1, 21), (12, 2)),
+    shift("1 Chronicles", 6, (1, 15), (5, 27)),
+    shift("1 Chronicles", 6, (16, 81), (6, 1)),
+    shift("2 Chronicles", 2, (1, 1), (1, 18)),
+    shift("2 Chronicles", 2, (2, 18), (2, 1)),
+    shift("Nehemiah", 4, (1, 6), (3, 33)),
+    shift("Nehemiah", 4, (7, 23), (4, 1)),
+    shift("Nehemiah", 9, (38, 38), (10, 1)),
+    shift("Nehemiah", 10, (1, 39), (10, 2)),
+    shift("Job", 41, (1, 8), (40, 25)),
+    shift("Job", 41, (9, 34), (41, 1)),
+    shift("Ecclesiastes", 5, (1, 1), (4, 17)),
+    shift("Ecclesiastes", 5, (2, 20), (5, 1)),
+    shift("Song Of Solomon", 6, (13, 13), (7, 1)),
+    shift("Song Of Solomon", 7, (1, 13), (7, 2)),
+    shift("Isaiah", 9, (1, 1), (8, 23)),
+    shift("Isaiah", 9, (2, 21), (9, 1)),
+    shift("Isaiah", 64, (1, 1), (63, 19)),
+    shift("Isaiah", 64, (2, 12), (64, 1)),
+    shift("Jeremiah", 9, (1, 1), (8, 23)),
+    shift("Jeremiah", 9, (2, 26), (9, 1)),
+    shift("Ezekiel", 20, (45, 49), (21, 1)),
+    shift("Ezekiel", 21, (1, 32), (21, 6)),
+    shift("Daniel", 4, (1, 3), (3, 31)),
+    shift("Daniel", 4, (4, 37), (4, 1)),
+    shift("Daniel", 5, (31, 31), (6, 1)),
+    shift("Daniel", 6, (1, 28), (6, 2)),
+    shift("Hosea", 11, (12, 12), (12, 1)),
+    shift("Hosea", 12, (1, 14), (12, 2)),
+    shift("Hosea", 13, (16, 16), (14, 1)),
+    shift("Hosea", 14, (1, 9), (14, 2)),
+    shift("Joel", 2, (28, 32), (3, 1)),
+    shift("Joel", 3, (1, 21), (4, 1)),
+    shift("Jonah", 1, (17, 17), (2, 1)),
+    shift("Jonah", 2, (1, 10), (2, 2)),
+    shift("Micah", 5, (1, 1), (4, 14)),
+    shift("Micah", 5, (2, 15), (5, 1)),
+    shift("Nahum", 1, (15, 15), (2, 1)),
+    shift("Nahum", 2, (1, 13), (2, 2)),
+    shift("Zechariah", 1, (18, 21), (2, 1)),
+    shift("Zechariah", 2, (1, 13), (2, 5)),
+    shift("Malachi", 4, (1, 6), (3, 19)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hebrew_chapter_breaks() {
+        let hebrew = Versification::from_name("Hebrew");
+        assert_eq!(hebrew.map("Malachi", 4, 1), (3, 19));
+        assert_eq!(hebrew.map("Malachi", 4, 6), (3, 24));
+        assert_eq!(hebrew.map("Joel", 2, 28), (3, 1));
+        assert_eq!(hebrew.map("Joel", 2, 27), (2, 27));
+        assert_eq!(hebrew.map("Joel", 3, 21), (4, 21));
+        assert_eq!(Versification::from_name("kjv").map("Malachi", 4, 1), (4, 1));
+    }
+
+    #[test]
+    fn test_psalm_titles_and_round_trips() {
+        let hebrew = Versification::Hebrew;
+        assert_eq!(hebrew.map("Psalm", 3, 1), (3, 2));
+        assert_eq!(hebrew.map("Psalm", 51, 1), (51, 3));
+        assert_eq!(hebrew.map("Psalm", 23, 1), (23, 1));
+        // The title of Psalm 3 has no KJV verse number
+        assert_eq!(hebrew.to_kjv("Psalm", 3, 1), None);
+
+        assert_eq!(hebrew.to_kjv("Malachi", 3, 19), Some((4, 1)));
+        assert_eq!(hebrew.to_kjv("Malachi", 3, 18), Some((3, 18)));
+        assert_eq!(hebrew.to_kjv("Joel", 4, 1), Some((3, 1)));
+        assert_eq!(
+            Versification::convert(hebrew, Versification::Kjv, "Jonah", 2, 1),
+            Some((1, 17))
+        );
+        for (book, chapter, verse) in [("Exodus", 8, 5), ("Daniel", 4, 37), ("Psalm", 60, 12)] {
+            let (c, v) = hebrew.map(book, chapter, verse);
+            assert_eq!(hebrew.to_kjv(book, c, v), Some((chapter, verse)));
+        }
+    }
+}
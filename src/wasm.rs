@@ -0,0 +1,56 @@
+//! JavaScript bindings, so the browser can detect references in typed text or
+//! speech results without the server. Build with
+//! `wasm-pack build --target web --no-default-features --features wasm`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::detect::{self, Session};
+
+fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value).map_err(Into::into)
+}
+
+/// bibleVerse("john three verse sixteen") → ["John 3:16"]
+#[wasm_bindgen(js_name = bibleVerse)]
+pub fn bible_verse(text: &str) -> Vec<String> {
+    detect::bible_verse(text)
+}
+
+/// detect("in the niv john three verse sixteen")
+///   → [{ reference: "John 3:16", translation: "NIV" }]
+#[wasm_bindgen]
+pub fn detect(text: &str) -> Result<JsValue, JsValue> {
+    to_js(&detect::detect(text))
+}
+
+/// Detection over a live transcript, see `detect::Session`
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct DetectionSession {
+    session: Session,
+}
+
+#[wasm_bindgen]
+impl DetectionSession {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New detections in the next partial transcript
+    pub fn feed(&mut self, text: &str) -> Result<JsValue, JsValue> {
+        to_js(&self.session.feed(text))
+    }
+
+    /// New detections in a final transcript, which ends the utterance
+    #[wasm_bindgen(js_name = feedFinal)]
+    pub fn feed_final(&mut self, text: &str) -> Result<JsValue, JsValue> {
+        to_js(&self.session.feed_final(text))
+    }
+
+    /// Every reference reported so far, oldest first
+    #[wasm_bindgen(getter)]
+    pub fn history(&self) -> Vec<String> {
+        self.session.history().to_vec()
+    }
+}